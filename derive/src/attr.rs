@@ -0,0 +1,179 @@
+use syn::{self, Attribute, Error, Ident, Lit, Meta, NestedMeta, Path};
+
+/// The options of a `#[json_api(...)]` attribute on the struct itself.
+#[derive(Default)]
+pub struct Container {
+    pub kind: Option<String>,
+    pub links: Option<Path>,
+    pub meta: Option<Path>,
+}
+
+impl Container {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut container = Container::default();
+
+        for item in options(attrs)? {
+            match item {
+                Meta::NameValue(ref item) if item.ident == "kind" => {
+                    container.kind = Some(string(&item.lit)?);
+                }
+                Meta::NameValue(ref item) if item.ident == "links" => {
+                    container.links = Some(path(&item.lit)?);
+                }
+                Meta::NameValue(ref item) if item.ident == "meta" => {
+                    container.meta = Some(path(&item.lit)?);
+                }
+                ref item => return Err(unknown(item)),
+            }
+        }
+
+        Ok(container)
+    }
+}
+
+/// How a field of the struct is represented in a resource object.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    Attribute,
+    HasMany,
+    HasOne,
+    Id,
+    Skip,
+}
+
+/// The options of a `#[json_api(...)]` attribute on a field of the struct.
+pub struct Field {
+    pub kind: Option<String>,
+    pub links: Option<Path>,
+    pub meta: Option<Path>,
+    pub rename: Option<String>,
+    pub role: Role,
+}
+
+impl Field {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut field = Field {
+            kind: None,
+            links: None,
+            meta: None,
+            rename: None,
+            role: Role::Attribute,
+        };
+
+        for item in options(attrs)? {
+            let role = match item {
+                Meta::Word(ref ident) if ident == "has_many" => Role::HasMany,
+                Meta::Word(ref ident) if ident == "has_one" => Role::HasOne,
+                Meta::Word(ref ident) if ident == "id" => Role::Id,
+                Meta::Word(ref ident) if ident == "skip" => Role::Skip,
+                Meta::NameValue(ref item) if item.ident == "kind" => {
+                    field.kind = Some(string(&item.lit)?);
+                    continue;
+                }
+                Meta::NameValue(ref item) if item.ident == "links" => {
+                    field.links = Some(path(&item.lit)?);
+                    continue;
+                }
+                Meta::NameValue(ref item) if item.ident == "meta" => {
+                    field.meta = Some(path(&item.lit)?);
+                    continue;
+                }
+                Meta::NameValue(ref item) if item.ident == "rename" => {
+                    field.rename = Some(string(&item.lit)?);
+                    continue;
+                }
+                ref item => return Err(unknown(item)),
+            };
+
+            if field.role != Role::Attribute {
+                let message = "a field can only have one of `id`, `has_one`, `has_many`, \
+                               and `skip`";
+                return Err(Error::new_spanned(item, message));
+            }
+
+            field.role = role;
+        }
+
+        let is_relationship = field.role == Role::HasMany || field.role == Role::HasOne;
+
+        let has_options =
+            field.kind.is_some() || field.links.is_some() || field.meta.is_some();
+
+        if !is_relationship && has_options {
+            let message = "`kind`, `links`, and `meta` can only be used with `has_one` or \
+                           `has_many`";
+            return Err(Error::new_spanned(&attrs[0], message));
+        }
+
+        if field.rename.is_some() && (field.role == Role::Id || field.role == Role::Skip) {
+            let message = "`rename` cannot be used with `id` or `skip`";
+            return Err(Error::new_spanned(&attrs[0], message));
+        }
+
+        Ok(field)
+    }
+}
+
+/// Returns the items of each `#[json_api(...)]` attribute in `attrs`.
+fn options(attrs: &[Attribute]) -> Result<Vec<Meta>, Error> {
+    let mut items = Vec::new();
+
+    for attr in attrs {
+        if !is_json_api(attr) {
+            continue;
+        }
+
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                let message = "expected an attribute like `#[json_api(...)]`";
+                return Err(Error::new_spanned(meta, message));
+            }
+        };
+
+        for item in list.nested {
+            match item {
+                NestedMeta::Meta(meta) => items.push(meta),
+                NestedMeta::Literal(lit) => {
+                    return Err(Error::new_spanned(lit, "unexpected literal"));
+                }
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+fn is_json_api(attr: &Attribute) -> bool {
+    let segments = &attr.path.segments;
+    segments.len() == 1 && segments[0].ident == "json_api"
+}
+
+fn name(item: &Meta) -> &Ident {
+    match *item {
+        Meta::List(ref list) => &list.ident,
+        Meta::NameValue(ref item) => &item.ident,
+        Meta::Word(ref ident) => ident,
+    }
+}
+
+fn path(lit: &Lit) -> Result<Path, Error> {
+    let value = string(lit)?;
+
+    syn::parse_str(&value).map_err(|_| {
+        let message = format!(r#""{}" is not a path to a function"#, value);
+        Error::new_spanned(lit, message)
+    })
+}
+
+fn string(lit: &Lit) -> Result<String, Error> {
+    match *lit {
+        Lit::Str(ref lit) => Ok(lit.value()),
+        _ => Err(Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn unknown(item: &Meta) -> Error {
+    let message = format!("unknown json_api option `{}`", name(item));
+    Error::new_spanned(item, message)
+}
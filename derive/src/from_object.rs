@@ -0,0 +1,85 @@
+use proc_macro2::TokenStream;
+use syn::{DeriveInput, Error};
+
+use attr::Role;
+use input::{Field, Input};
+
+/// Whether `FromObject` or `FromNewObject` is derived.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Target {
+    NewObject,
+    Object,
+}
+
+pub fn expand(input: &DeriveInput, target: Target) -> Result<TokenStream, Error> {
+    let Input { fields, kind, .. } = Input::from_ast(input)?;
+    let mut members = Vec::with_capacity(fields.len());
+    let mut values = Vec::with_capacity(fields.len());
+
+    for field in &fields {
+        let value = match field.attr.role {
+            Role::Attribute => {
+                let name = field.name()?;
+                quote!(extract.attribute(#name)?)
+            }
+            Role::HasMany => {
+                let (name, kind) = (field.name()?, related_kind(field)?);
+                quote!(extract.has_many(#name, #kind)?)
+            }
+            Role::HasOne => {
+                let (name, kind) = (field.name()?, related_kind(field)?);
+                quote!(extract.has_one(#name, #kind)?)
+            }
+            Role::Id => match target {
+                Target::NewObject => quote!(extract.optional_id()?.unwrap_or_default()),
+                Target::Object => quote!(extract.id()?),
+            },
+            Role::Skip => quote!(::std::default::Default::default()),
+        };
+
+        members.push(&field.member);
+        values.push(value);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (path, method, object, constructor) = match target {
+        Target::NewObject => (
+            quote!(::json_api::doc::FromNewObject),
+            quote!(from_new_object),
+            quote!(::json_api::doc::NewObject),
+            quote!(::json_api::doc::Extractor::from_new_object),
+        ),
+        Target::Object => (
+            quote!(::json_api::doc::FromObject),
+            quote!(from_object),
+            quote!(::json_api::doc::Object),
+            quote!(::json_api::doc::Extractor::from_object),
+        ),
+    };
+
+    Ok(quote! {
+        impl #impl_generics #path for #ident #ty_generics #where_clause {
+            fn #method(object: #object) -> Result<Self, ::json_api::Error> {
+                #[allow(unused_mut)]
+                let mut extract = #constructor(object, #kind)?;
+
+                Ok(#ident {
+                    #(#members: #values,)*
+                })
+            }
+        }
+    })
+}
+
+/// Returns the type of the resource(s) that a relationship field references.
+fn related_kind(field: &Field) -> Result<&str, Error> {
+    match field.attr.kind {
+        Some(ref kind) => Ok(kind),
+        None => {
+            let message = "a relationship must have a `kind` option to be converted from \
+                           a resource object";
+            Err(Error::new_spanned(&field.member, message))
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use proc_macro2::Span;
+use syn::{Data, DeriveInput, Error, Fields, Index, Member};
+
+use attr::{self, Container, Role};
+
+/// A field of the struct and its options.
+pub struct Field {
+    pub attr: attr::Field,
+    pub member: Member,
+    name: Option<String>,
+}
+
+impl Field {
+    /// Returns the name of the attribute or relationship that the field is rendered as.
+    pub fn name(&self) -> Result<&str, Error> {
+        match self.name {
+            Some(ref name) => Ok(name),
+            None => {
+                let message = "a field without a name must be `id`, `skip`, or have \
+                               a `rename` option";
+                Err(Error::new_spanned(&self.member, message))
+            }
+        }
+    }
+}
+
+/// The options of a struct and its fields that are shared by each derive.
+pub struct Input {
+    pub container: Container,
+    pub fields: Vec<Field>,
+    pub id: Option<Member>,
+    pub kind: String,
+}
+
+impl Input {
+    pub fn from_ast(input: &DeriveInput) -> Result<Self, Error> {
+        let container = Container::from_attrs(&input.attrs)?;
+        let mut fields = fields(input)?;
+
+        let kind = match container.kind {
+            Some(ref kind) => kind.clone(),
+            None => {
+                let message = "missing `#[json_api(kind = \"...\")]` attribute";
+                return Err(Error::new_spanned(&input.ident, message));
+            }
+        };
+
+        let id = id(&mut fields)?;
+
+        Ok(Input {
+            container,
+            fields,
+            id,
+            kind,
+        })
+    }
+}
+
+fn fields(input: &DeriveInput) -> Result<Vec<Field>, Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => {
+            let message = "json_api derives can only be used with structs";
+            return Err(Error::new_spanned(input, message));
+        }
+    };
+
+    let items: Vec<_> = match *fields {
+        Fields::Named(ref fields) => fields.named.iter().collect(),
+        Fields::Unnamed(ref fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let attr = attr::Field::from_attrs(&field.attrs)?;
+            let (member, name) = match field.ident {
+                Some(ref ident) => (Member::Named(ident.clone()), Some(ident.to_string())),
+                None => {
+                    let index = Index {
+                        index: idx as u32,
+                        span: Span::call_site(),
+                    };
+
+                    (Member::Unnamed(index), None)
+                }
+            };
+
+            Ok(Field {
+                member,
+                name: attr.rename.clone().or(name),
+                attr,
+            })
+        })
+        .collect()
+}
+
+/// Returns the field that is used as the id of the resource. If no field has the `id`
+/// option, the field named `id` is marked as the id.
+fn id(fields: &mut [Field]) -> Result<Option<Member>, Error> {
+    let position = {
+        let mut ids = fields
+            .iter()
+            .enumerate()
+            .filter(|&(_, field)| field.attr.role == Role::Id);
+
+        match (ids.next(), ids.next()) {
+            (Some((idx, _)), None) => Some(idx),
+            (Some(_), Some((_, field))) => {
+                let message = "only one field can have the `id` option";
+                return Err(Error::new_spanned(&field.member, message));
+            }
+            _ => None,
+        }
+    };
+
+    let position = position.or_else(|| {
+        fields.iter().position(|field| match field.member {
+            Member::Named(ref ident) => ident == "id" && field.attr.role == Role::Attribute,
+            Member::Unnamed(_) => false,
+        })
+    });
+
+    Ok(position.map(|idx| {
+        let field = &mut fields[idx];
+
+        field.attr.role = Role::Id;
+        field.member.clone()
+    }))
+}
@@ -0,0 +1,209 @@
+//! Custom derives for the [`Resource`], [`FromObject`], and [`FromNewObject`] traits of
+//! the [json-api] crate.
+//!
+//! `#[derive(Resource)]` generates the same implementation as the [`resource!`] macro.
+//! Unlike the macro, it can be used with generic structs and the attributes of a
+//! resource do not have to be listed. Each field that is not the id, a relationship, or
+//! skipped is rendered as an attribute. The attributes and relationships are also
+//! listed in the schema that is returned by `Resource::schema`. The keys of the links
+//! and meta that are returned by hooks are not known until a resource is rendered, so
+//! they are not part of the schema.
+//!
+//! # Options
+//!
+//! The type of the resource is required and is set on the struct.
+//!
+//! - `#[json_api(kind = "posts")]` sets the type of the resource.
+//! - `#[json_api(links = "path")]` calls the function at `path` with `&self` and adds
+//!   the returned `Result<Map<Key, Link>, Error>` to the links of the resource object.
+//! - `#[json_api(meta = "path")]` calls the function at `path` with `&self` and adds the
+//!   returned `Result<Map, Error>` to the meta of the resource object and identifier.
+//!
+//! The following options can be used on the fields of the struct.
+//!
+//! - `#[json_api(id)]` uses the field as the id of the resource. The field must
+//!   implement `ToString`. A field named `id` is used if no field has this option.
+//! - `#[json_api(has_one)]` renders the field as a to-one relationship. The field must
+//!   have an `as_ref` method that returns an `Option<&T>` where `T: Resource`.
+//! - `#[json_api(has_many)]` renders the field as a to-many relationship. The field
+//!   must have an `iter` method that returns an iterator of `&T` where `T: Resource`.
+//! - `#[json_api(skip)]` does not render the field.
+//! - `#[json_api(rename = "name")]` renders the field with a different name.
+//! - `#[json_api(links = "path")]` and `#[json_api(meta = "path")]` add the links and
+//!   meta returned by a function to a relationship.
+//!
+//! The keys of the links and meta returned by a function are converted to the casing
+//! convention of the [`RenderPolicy`], like the attributes and relationships.
+//!
+//! Fields that are rendered as attributes must implement `Serialize`. Bounds are not
+//! added to the type parameters of a generic struct, so they have to be declared on
+//! the struct itself.
+//!
+//! # Example
+//!
+//! ```
+//! extern crate json_api;
+//! #[macro_use]
+//! extern crate json_api_derive;
+//! extern crate serde;
+//!
+//! use json_api::Error;
+//! use json_api::doc::Link;
+//! use json_api::value::{Key, Map};
+//! use serde::Serialize;
+//!
+//! #[derive(Resource)]
+//! #[json_api(kind = "articles", links = "Post::links")]
+//! struct Post<T: Serialize> {
+//!     id: u64,
+//!     body: String,
+//!     #[json_api(rename = "headline")]
+//!     title: String,
+//!     extra: T,
+//!     #[json_api(has_one)]
+//!     author: Option<User>,
+//!     #[json_api(has_many)]
+//!     comments: Vec<Comment>,
+//!     #[json_api(skip)]
+//!     draft: bool,
+//! }
+//!
+//! impl<T: Serialize> Post<T> {
+//!     fn links(&self) -> Result<Map<Key, Link>, Error> {
+//!         let mut links = Map::new();
+//!         links.insert("self".parse()?, format!("/articles/{}", self.id).parse()?);
+//!         Ok(links)
+//!     }
+//! }
+//! #
+//! # #[derive(Resource)]
+//! # #[json_api(kind = "users")]
+//! # struct User {
+//! #     id: u64,
+//! # }
+//! #
+//! # #[derive(Resource)]
+//! # #[json_api(kind = "comments")]
+//! # struct Comment(#[json_api(id)] u64);
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! # Deserialization
+//!
+//! `#[derive(FromObject)]` and `#[derive(FromNewObject)]` create a struct from the
+//! resource object in the primary data of a request. They use the same options, with
+//! a few differences.
+//!
+//! - Each attribute is deserialized with `Deserialize`. A missing attribute is
+//!   deserialized from `null`, so an `Option<T>` field is optional.
+//! - A relationship field contains the id(s) of the related resource(s) and requires
+//!   the type of the related resource(s) with `#[json_api(kind = "...")]`. A `has_one`
+//!   field must be an `Option<T>` and a `has_many` field must be a `Vec<T>`, where `T`
+//!   implements `FromStr`. An identifier of another type is an error.
+//! - The id field is optional and must implement `FromStr`. With `FromNewObject`, it
+//!   must also implement `Default`, which is used if the client did not provide an id.
+//! - A skipped field must implement `Default`.
+//!
+//! A derived implementation cannot tell a missing attribute or relationship apart from
+//! one that is set to `null`. A request that updates a resource only contains the
+//! members that change, so a type that is used to apply such an update should
+//! implement `FromObject` by hand with the `contains` and `optional_*` methods of an
+//! [`Extractor`].
+//!
+//! ```
+//! extern crate json_api;
+//! #[macro_use]
+//! extern crate json_api_derive;
+//! #
+//! # use json_api::Error;
+//!
+//! use json_api::doc;
+//!
+//! #[derive(FromNewObject)]
+//! #[json_api(kind = "articles")]
+//! struct NewArticle {
+//!     title: String,
+//!     #[json_api(has_one, kind = "people")]
+//!     author: Option<u64>,
+//!     #[json_api(has_many, kind = "tags")]
+//!     tags: Vec<String>,
+//! }
+//! #
+//! # fn example() -> Result<(), Error> {
+//!
+//! let article: NewArticle = doc::new_object_from_str(r#"{
+//!     "data": {
+//!         "type": "articles",
+//!         "attributes": { "title": "JSON API paints my bikeshed!" },
+//!         "relationships": {
+//!             "author": { "data": { "type": "people", "id": "9" } },
+//!             "tags": { "data": [{ "type": "tags", "id": "rust" }] }
+//!         }
+//!     }
+//! }"#)?;
+//!
+//! assert_eq!(article.author, Some(9));
+//! assert_eq!(article.tags, vec!["rust".to_owned()]);
+//! #
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+//!
+//! [`Extractor`]: https://docs.rs/json-api/0.4/json_api/doc/struct.Extractor.html
+//! [`FromNewObject`]: https://docs.rs/json-api/0.4/json_api/doc/trait.FromNewObject.html
+//! [`FromObject`]: https://docs.rs/json-api/0.4/json_api/doc/trait.FromObject.html
+//! [`Resource`]: https://docs.rs/json-api/0.4/json_api/trait.Resource.html
+//! [`RenderPolicy`]: https://docs.rs/json-api/0.4/json_api/view/struct.RenderPolicy.html
+//! [`resource!`]: https://docs.rs/json-api/0.4/json_api/macro.resource.html
+//! [json-api]: https://docs.rs/json-api/0.4
+
+#![recursion_limit = "256"]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+mod attr;
+mod from_object;
+mod input;
+mod resource;
+
+use proc_macro::TokenStream;
+use syn::DeriveInput;
+
+use from_object::Target;
+
+#[proc_macro_derive(Resource, attributes(json_api))]
+pub fn derive_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    resource::expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(FromObject, attributes(json_api))]
+pub fn derive_from_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    from_object::expand(&input, Target::Object)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(FromNewObject, attributes(json_api))]
+pub fn derive_from_new_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    from_object::expand(&input, Target::NewObject)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
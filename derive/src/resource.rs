@@ -0,0 +1,259 @@
+use proc_macro2::TokenStream;
+use syn::{DeriveInput, Error, Path};
+
+use attr::Role;
+use input::{Field, Input};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let Input {
+        container,
+        fields,
+        id,
+        kind,
+        ..
+    } = Input::from_ast(input)?;
+
+    let id = match id {
+        Some(id) => id,
+        None => {
+            let message = "missing a field named `id` or a field with `#[json_api(id)]`";
+            return Err(Error::new_spanned(&input.ident, message));
+        }
+    };
+
+    let mut attrs = Vec::new();
+    let mut related = Vec::new();
+    let mut schema = Vec::new();
+
+    for field in &fields {
+        let (name, member) = match field.attr.role {
+            Role::Id | Role::Skip => continue,
+            _ => (field.name()?, &field.member),
+        };
+
+        match field.attr.role {
+            Role::HasMany => {
+                related.push(has_many(field, name));
+                schema.push(quote! {
+                    schema.relationships.insert(
+                        #name.parse()?,
+                        ::json_api::schema::Relation::HasMany(kind_of(|this: &Self| {
+                            this.#member.iter().next()
+                        })),
+                    );
+                });
+            }
+            Role::HasOne => {
+                related.push(has_one(field, name));
+                schema.push(quote! {
+                    schema.relationships.insert(
+                        #name.parse()?,
+                        ::json_api::schema::Relation::HasOne(kind_of(|this: &Self| {
+                            this.#member.as_ref()
+                        })),
+                    );
+                });
+            }
+            _ => {
+                attrs.push(attribute(field, name));
+                schema.push(quote! {
+                    schema.attributes.insert(#name.parse()?);
+                });
+            }
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let links = extend(quote!(obj.links), quote!(ctx), container.links.as_ref());
+    let ident_meta = extend(quote!(ident.meta), quote!(_ctx), container.meta.as_ref());
+    let obj_meta = extend(quote!(obj.meta), quote!(ctx), container.meta.as_ref());
+
+    Ok(quote! {
+        impl #impl_generics ::json_api::Resource for #ident #ty_generics #where_clause {
+            fn kind() -> ::json_api::value::Key {
+                ::json_api::value::Key::from_raw(#kind.to_owned())
+            }
+
+            fn id(&self) -> String {
+                ::std::string::ToString::to_string(&self.#id)
+            }
+
+            fn to_ident(
+                &self,
+                _ctx: &mut ::json_api::view::Context,
+            ) -> Result<::json_api::doc::Identifier, ::json_api::Error> {
+                let mut ident = {
+                    let kind = <Self as ::json_api::Resource>::kind();
+                    let id = ::json_api::Resource::id(self);
+
+                    ::json_api::doc::Identifier::new(kind, id)
+                };
+
+                #ident_meta
+
+                Ok(ident)
+            }
+
+            fn to_object(
+                &self,
+                ctx: &mut ::json_api::view::Context,
+            ) -> Result<::json_api::doc::Object, ::json_api::Error> {
+                #[allow(dead_code)]
+                fn item_kind<T: ::json_api::Resource>(_: &T) -> ::json_api::value::Key {
+                    T::kind()
+                }
+
+                #[allow(dead_code)]
+                fn iter_kind<'a, I, T>(_: &I) -> ::json_api::value::Key
+                where
+                    I: Iterator<Item = &'a T>,
+                    T: ::json_api::Resource + 'a,
+                {
+                    T::kind()
+                }
+
+                let mut obj = {
+                    let kind = <Self as ::json_api::Resource>::kind();
+                    let id = ::json_api::Resource::id(self);
+
+                    ::json_api::doc::Object::new(kind, id)
+                };
+
+                #(#attrs)*
+                #links
+                #obj_meta
+                #(#related)*
+
+                Ok(obj)
+            }
+
+            fn schema() -> Result<::json_api::schema::Schema, ::json_api::Error> {
+                #[allow(dead_code)]
+                fn kind_of<S, T, F>(_: F) -> ::json_api::value::Key
+                where
+                    F: Fn(&S) -> Option<&T>,
+                    T: ::json_api::Resource,
+                {
+                    T::kind()
+                }
+
+                let mut schema = {
+                    let kind = <Self as ::json_api::Resource>::kind();
+                    ::json_api::schema::Schema::new(kind)
+                };
+
+                #(#schema)*
+
+                Ok(schema)
+            }
+        }
+    })
+}
+
+fn attribute(field: &Field, name: &str) -> TokenStream {
+    let member = &field.member;
+
+    quote! {
+        {
+            let key = #name.parse::<::json_api::value::Key>()?;
+
+            if ctx.field(&key) {
+                let value = ::json_api::to_value(&self.#member)?;
+                obj.attributes.insert(ctx.key(#name)?, value);
+            }
+        }
+    }
+}
+
+fn has_many(field: &Field, name: &str) -> TokenStream {
+    let member = &field.member;
+    let links = extend(quote!(rel.links), quote!(ctx), field.attr.links.as_ref());
+    let meta = extend(quote!(rel.meta), quote!(ctx), field.attr.meta.as_ref());
+
+    quote! {
+        {
+            let key = #name.parse::<::json_api::value::Key>()?;
+
+            if ctx.field(&key) {
+                let mut rel = ::json_api::doc::Relationship::new({
+                    let mut ctx = ctx.fork(iter_kind(&self.#member.iter()), &key);
+                    let mut data = match self.#member.iter().size_hint() {
+                        (_, Some(size)) => Vec::with_capacity(size),
+                        _ => Vec::new(),
+                    };
+
+                    if ctx.included() {
+                        for item in self.#member.iter() {
+                            let object = ::json_api::Resource::to_object(item, &mut ctx)?;
+                            let ident = ::json_api::doc::Identifier::from(&object);
+
+                            ctx.try_include(object)?;
+                            data.push(ident);
+                        }
+                    } else {
+                        for item in self.#member.iter() {
+                            data.push(::json_api::Resource::to_ident(item, &mut ctx)?);
+                        }
+                    }
+
+                    data.into()
+                });
+
+                #links
+                #meta
+
+                obj.relationships.insert(ctx.key(#name)?, rel);
+            }
+        }
+    }
+}
+
+fn has_one(field: &Field, name: &str) -> TokenStream {
+    let member = &field.member;
+    let links = extend(quote!(rel.links), quote!(ctx), field.attr.links.as_ref());
+    let meta = extend(quote!(rel.meta), quote!(ctx), field.attr.meta.as_ref());
+
+    quote! {
+        {
+            let key = #name.parse::<::json_api::value::Key>()?;
+
+            if ctx.field(&key) {
+                let mut rel = ::json_api::doc::Relationship::new({
+                    let mut data = None;
+
+                    if let Some(item) = self.#member.as_ref() {
+                        let mut ctx = ctx.fork(item_kind(item), &key);
+
+                        data = Some(::json_api::Resource::to_ident(item, &mut ctx)?);
+
+                        if ctx.included() {
+                            let object = ::json_api::Resource::to_object(item, &mut ctx)?;
+                            ctx.try_include(object)?;
+                        }
+                    }
+
+                    data.into()
+                });
+
+                #links
+                #meta
+
+                obj.relationships.insert(ctx.key(#name)?, rel);
+            }
+        }
+    }
+}
+
+/// Extends the map `target` with the value returned by the hook at `path`. Each key is
+/// converted to the casing convention of the render policy of the context `ctx`.
+fn extend(target: TokenStream, ctx: TokenStream, path: Option<&Path>) -> TokenStream {
+    match path {
+        Some(path) => quote! {
+            for (key, value) in #path(self)? {
+                #target.insert(#ctx.key(&key)?, value);
+            }
+        },
+        None => TokenStream::new(),
+    }
+}
@@ -0,0 +1,72 @@
+extern crate json_api;
+#[macro_use]
+extern crate json_api_derive;
+
+use json_api::doc::{self, ErrorObject};
+
+#[derive(Debug, FromObject, FromNewObject, PartialEq)]
+#[json_api(kind = "articles")]
+struct Article {
+    id: u64,
+    title: String,
+    #[json_api(rename = "body")]
+    text: Option<String>,
+    #[json_api(has_one, kind = "people")]
+    author: Option<String>,
+    #[json_api(has_many, kind = "comments", rename = "replies")]
+    comments: Vec<u64>,
+    #[json_api(skip)]
+    views: u64,
+}
+
+const ARTICLE: &str = r#"{
+    "data": {
+        "type": "articles",
+        "id": "1",
+        "attributes": { "title": "Rails is Omakase" },
+        "relationships": {
+            "author": { "data": { "type": "people", "id": "dhh" } },
+            "replies": {
+                "data": [
+                    { "type": "comments", "id": "5" },
+                    { "type": "comments", "id": "12" }
+                ]
+            }
+        }
+    }
+}"#;
+
+#[test]
+fn derive_from_object() {
+    let article: Article = doc::object_from_str(ARTICLE).unwrap();
+
+    assert_eq!(
+        article,
+        Article {
+            id: 1,
+            title: "Rails is Omakase".to_owned(),
+            text: None,
+            author: Some("dhh".to_owned()),
+            comments: vec![5, 12],
+            views: 0,
+        }
+    );
+
+    let new: Article = doc::new_object_from_str(&ARTICLE.replace(r#""id": "1","#, ""))
+        .unwrap();
+
+    assert_eq!(new.id, 0);
+    assert_eq!(new.comments, vec![5, 12]);
+}
+
+#[test]
+fn derive_from_object_pointer() {
+    let source = ARTICLE.replace(
+        r#""type": "comments", "id": "12""#,
+        r#""type": "people", "id": "12""#,
+    );
+    let e = doc::object_from_str::<Article>(&source).unwrap_err();
+    let pointer = ErrorObject::from(&e).source.and_then(|source| source.pointer);
+
+    assert_eq!(pointer, Some("/data/relationships/replies/data/1/type".to_owned()));
+}
@@ -0,0 +1,250 @@
+#[macro_use]
+extern crate json_api;
+#[macro_use]
+extern crate json_api_derive;
+extern crate serde;
+
+use json_api::doc::{Link, Object};
+use json_api::query;
+use json_api::schema::Relation;
+use json_api::value::{Key, Map, Set, Value};
+use json_api::value::fields::Case;
+use json_api::view::{Context, Render, RenderPolicy};
+use json_api::{Document, Error, Resource};
+use serde::Serialize;
+
+#[derive(Resource)]
+#[json_api(kind = "articles", links = "Article::links", meta = "Article::meta")]
+struct Article<T: Serialize> {
+    id: u64,
+    body: String,
+    #[json_api(rename = "headline")]
+    title: String,
+    published_at: T,
+    #[json_api(has_one, links = "Article::author_links")]
+    author: Option<Person>,
+    #[json_api(has_many, meta = "Article::comments_meta")]
+    comments: Vec<Comment>,
+    #[json_api(skip)]
+    #[allow(dead_code)]
+    draft: bool,
+}
+
+impl<T: Serialize> Article<T> {
+    fn links(&self) -> Result<Map<Key, Link>, Error> {
+        let mut links = Map::new();
+        links.insert("self".parse()?, format!("/articles/{}", self.id).parse()?);
+        Ok(links)
+    }
+
+    fn meta(&self) -> Result<Map, Error> {
+        let mut meta = Map::new();
+        meta.insert("words".parse()?, (self.body.split(' ').count() as u64).into());
+        Ok(meta)
+    }
+
+    fn author_links(&self) -> Result<Map<Key, Link>, Error> {
+        let mut links = Map::new();
+        let href = format!("/articles/{}/author", self.id);
+
+        links.insert("related".parse()?, href.parse()?);
+        Ok(links)
+    }
+
+    fn comments_meta(&self) -> Result<Map, Error> {
+        let mut meta = Map::new();
+        meta.insert("total".parse()?, (self.comments.len() as u64).into());
+        Ok(meta)
+    }
+}
+
+#[derive(Resource)]
+#[json_api(kind = "people")]
+struct Person {
+    #[json_api(id)]
+    handle: String,
+    name: String,
+}
+
+#[derive(Resource)]
+#[json_api(kind = "comments")]
+struct Comment(#[json_api(id)] u64, #[json_api(rename = "body")] String);
+
+struct Post {
+    id: u64,
+    body: String,
+    title: String,
+    published_at: &'static str,
+    author: Option<Person>,
+    comments: Vec<Comment>,
+}
+
+resource!(Post, |&self| {
+    kind "articles";
+    id self.id;
+
+    attrs body;
+    attr "headline", &self.title;
+    attr "published-at", &self.published_at;
+
+    has_one "author", {
+        data self.author.as_ref();
+        link "related", format!("/articles/{}/author", self.id);
+    }
+
+    has_many "comments", {
+        data self.comments.iter();
+        meta "total", { self.comments.len() }
+    }
+
+    link "self", format!("/articles/{}", self.id);
+    meta "words", { self.body.split(' ').count() }
+});
+
+#[derive(Resource)]
+#[json_api(kind = "drafts", links = "Draft::links", meta = "Draft::meta")]
+struct Draft {
+    id: u64,
+    word_count: u64,
+    #[json_api(has_one, meta = "Draft::meta")]
+    last_editor: Option<Person>,
+}
+
+impl Draft {
+    fn links(&self) -> Result<Map<Key, Link>, Error> {
+        let mut links = Map::new();
+        links.insert("edit-form".parse()?, format!("/drafts/{}/edit", self.id).parse()?);
+        Ok(links)
+    }
+
+    fn meta(&self) -> Result<Map, Error> {
+        let mut meta = Map::new();
+        meta.insert("word-count".parse()?, self.word_count.into());
+        Ok(meta)
+    }
+}
+
+fn person() -> Person {
+    Person {
+        handle: "dhh".to_owned(),
+        name: "David Heinemeier Hansson".to_owned(),
+    }
+}
+
+fn comments() -> Vec<Comment> {
+    vec![
+        Comment(1, "First!".to_owned()),
+        Comment(2, "Like!".to_owned()),
+    ]
+}
+
+fn render<T>(value: &T, query: &str) -> Value
+where
+    for<'a> &'a T: Render<Object>,
+{
+    let query = query::from_str(query).unwrap();
+    let doc: Document<Object> = value.render(Some(&query)).unwrap();
+
+    json_api::to_value(doc).unwrap()
+}
+
+#[test]
+fn derive_matches_resource_macro() {
+    let article = Article {
+        id: 1,
+        body: "Rails is Omakase".to_owned(),
+        title: "Omakase".to_owned(),
+        published_at: "2017-01-01",
+        author: Some(person()),
+        comments: comments(),
+        draft: false,
+    };
+
+    let post = Post {
+        id: 1,
+        body: "Rails is Omakase".to_owned(),
+        title: "Omakase".to_owned(),
+        published_at: "2017-01-01",
+        author: Some(person()),
+        comments: comments(),
+    };
+
+    let queries = [
+        "",
+        "include=author,comments",
+        "include=comments&fields[articles]=headline,comments&fields[comments]=body",
+        "include=author&fields[articles]=published-at,author&fields[people]=name",
+    ];
+
+    for query in &queries {
+        assert_eq!(render(&article, query), render(&post, query), "{}", query);
+    }
+}
+
+#[test]
+fn derive_ident_meta() {
+    let article = Article {
+        id: 1,
+        body: "Rails is Omakase".to_owned(),
+        title: "Omakase".to_owned(),
+        published_at: 2017,
+        author: None,
+        comments: Vec::new(),
+        draft: true,
+    };
+
+    let mut included = Set::new();
+    let mut ctx = Context::new(Article::<u64>::kind(), None, &mut included);
+    let ident = article.to_ident(&mut ctx).unwrap();
+
+    assert_eq!(ident.meta.get("words"), Some(&Value::from(3)));
+}
+
+#[test]
+fn derive_hooks_with_policy() {
+    let draft = Draft {
+        id: 1,
+        word_count: 3,
+        last_editor: Some(person()),
+    };
+
+    let mut policy = RenderPolicy::new();
+    policy.case = Case::Camel;
+
+    let mut included = Set::new();
+    let mut ctx = Context::with_policy(Draft::kind(), None, &mut included, &policy)
+        .unwrap();
+    let ident = draft.to_ident(&mut ctx).unwrap();
+    let obj = draft.to_object(&mut ctx).unwrap();
+    let count = Value::from(3);
+
+    assert_eq!(ident.meta.get("wordCount"), Some(&count));
+    assert_eq!(obj.attributes.get("wordCount"), Some(&count));
+    assert_eq!(obj.meta.get("wordCount"), Some(&count));
+    assert!(obj.links.contains_key("editForm"));
+
+    let rel = obj.relationships.get("lastEditor").unwrap();
+    assert_eq!(rel.meta.get("wordCount"), Some(&count));
+}
+
+#[test]
+fn derive_schema() {
+    let schema = Article::<u64>::schema().unwrap();
+    let attributes = schema.attributes.iter().map(|key| &**key).collect::<Vec<_>>();
+
+    assert_eq!(attributes, vec!["body", "headline", "published-at"]);
+    assert_eq!(
+        schema.relationships.get("author"),
+        Some(&Relation::HasOne("people".parse().unwrap()))
+    );
+    assert_eq!(
+        schema.relationships.get("comments"),
+        Some(&Relation::HasMany("comments".parse().unwrap()))
+    );
+    assert_eq!(schema, {
+        let mut schema = Post::schema().unwrap();
+        schema.links.clear();
+        schema.meta.clear();
+        schema
+    });
+}
@@ -2,8 +2,8 @@ use std::ops::{Deref, DerefMut};
 
 use serde::de::DeserializeOwned;
 
-use json_api::doc::{NewObject, Object};
-use json_api::query::{self, Page, Query as JsonApiQuery, Sort};
+use json_api::doc::{self, ErrorObject, FromNewObject, FromObject, NewObject, Object};
+use json_api::query::{self, Pagination, Query as JsonApiQuery, Sort};
 use json_api::value::collections::{map, set, Set};
 use json_api::value::{Key, Path, Value};
 use json_api::{self, Error};
@@ -49,6 +49,45 @@ impl<T: DeserializeOwned> FromData for Create<T> {
     }
 }
 
+/// The resource object in the body of a request that creates a resource, converted with
+/// `FromNewObject`.
+#[derive(Debug)]
+pub struct CreateTyped<T: FromNewObject>(pub T);
+
+impl<T: FromNewObject> CreateTyped<T> {
+    /// Consumes the `CreateTyped` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FromNewObject> Deref for CreateTyped<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: FromNewObject> DerefMut for CreateTyped<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: FromNewObject> FromData for CreateTyped<T> {
+    type Error = Error;
+
+    fn from_data(_: &Request, data: Data) -> data::Outcome<Self, Self::Error> {
+        let reader = data.open();
+
+        match doc::new_object_from_reader(reader) {
+            Ok(value) => Outcome::Success(CreateTyped(value)),
+            Err(e) => fail(e),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Update<T: DeserializeOwned>(pub T);
 
@@ -86,6 +125,45 @@ impl<T: DeserializeOwned> FromData for Update<T> {
     }
 }
 
+/// The resource object in the body of a request that updates a resource, converted with
+/// `FromObject`.
+#[derive(Debug)]
+pub struct UpdateTyped<T: FromObject>(pub T);
+
+impl<T: FromObject> UpdateTyped<T> {
+    /// Consumes the `UpdateTyped` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FromObject> Deref for UpdateTyped<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: FromObject> DerefMut for UpdateTyped<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: FromObject> FromData for UpdateTyped<T> {
+    type Error = Error;
+
+    fn from_data(_: &Request, data: Data) -> data::Outcome<Self, Self::Error> {
+        let reader = data.open();
+
+        match doc::object_from_reader(reader) {
+            Ok(value) => Outcome::Success(UpdateTyped(value)),
+            Err(e) => fail(e),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Query {
     inner: JsonApiQuery,
@@ -111,8 +189,8 @@ impl Query {
         self.inner.include.iter()
     }
 
-    pub fn page(&self) -> Option<Page> {
-        self.inner.page
+    pub fn page(&self) -> Option<&Pagination> {
+        self.inner.page.as_ref()
     }
 
     pub fn sort(&self) -> set::Iter<Sort> {
@@ -153,5 +231,10 @@ fn fail<T, F>(e: Error) -> Outcome<T, (Status, Error), F> {
         eprintln!("{:?}", e);
     }
 
-    Outcome::Failure((Status::BadRequest, e))
+    let status = ErrorObject::from(&e)
+        .status
+        .and_then(|value| Status::from_code(value.as_u16()))
+        .unwrap_or(Status::BadRequest);
+
+    Outcome::Failure((status, e))
 }
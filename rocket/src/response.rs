@@ -2,7 +2,7 @@ use std::io::Cursor;
 use std::iter::FromIterator;
 use std::ops::{Deref, DerefMut};
 
-use json_api::doc::Object;
+use json_api::doc::{Document, ErrorObject, Object};
 use json_api::{self, Error, Resource};
 use rocket::Outcome;
 use rocket::http::Status;
@@ -102,6 +102,54 @@ impl<T: Resource> Responder<'static> for Created<T> {
     }
 }
 
+/// Responds with an error document that describes the wrapped error.
+///
+/// The status of the response is the status of the error object that is returned by
+/// `ErrorObject::from`. A handler can accept `Result<Create<T>, Error>` as its data
+/// guard to respond with the exact problem of an invalid request document.
+#[derive(Debug)]
+pub struct Failure(pub Error);
+
+impl Failure {
+    /// Consumes the [`Failure`] wrapper and returns the wrapped value.
+    ///
+    /// [`Failure`]: ./struct.Failure.html
+    pub fn into_inner(self) -> Error {
+        self.0
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(error: Error) -> Self {
+        Failure(error)
+    }
+}
+
+impl Responder<'static> for Failure {
+    fn respond_to(self, _: &Request) -> Result<Response<'static>, Status> {
+        let error = ErrorObject::from(&self.0);
+        let status = error
+            .status
+            .and_then(|value| Status::from_code(value.as_u16()))
+            .unwrap_or(Status::InternalServerError);
+
+        if status == Status::InternalServerError {
+            return fail(self.0);
+        }
+
+        Document::<Object>::builder()
+            .error(error)
+            .build()
+            .and_then(|doc| json_api::to_vec(doc, None))
+            .map(with_body)
+            .or_else(fail)
+            .map(|mut resp| {
+                resp.set_status(status);
+                resp
+            })
+    }
+}
+
 #[derive(Debug)]
 pub struct Member<T>(pub T);
 
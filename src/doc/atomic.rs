@@ -0,0 +1,762 @@
+//! Types for the *[Atomic Operations]* extension.
+//!
+//! The extension allows a client to perform multiple operations in a single request.
+//! The extension is negotiated with the `ext` parameter of the media type, so the
+//! `jsonapi` member of a document is optional. If it is present, it cannot declare a
+//! version of the specification earlier than 1.1.
+//!
+//! [Atomic Operations]: https://jsonapi.org/ext/atomic
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{self, Value as JsonValue};
+
+use doc::{Data, Document, ErrorObject, Identifier, JsonApi, Link, NewObject, Object, Version};
+use value::{Key, Map};
+
+/// The URI of the Atomic Operations extension.
+pub const EXT_URI: &str = "https://jsonapi.org/ext/atomic";
+
+/// A request document containing an ordered list of operations.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::atomic::{Operation, Operations};
+///
+/// let doc: Operations = serde_json::from_str(r#"{
+///     "atomic:operations": [{
+///         "op": "remove",
+///         "ref": { "type": "articles", "id": "13" }
+///     }],
+///     "jsonapi": { "version": "1.1" }
+/// }"#)?;
+///
+/// match doc.operations[0] {
+///     Operation::Remove { ref target, .. } => {
+///         assert_eq!(target.as_ref().map(|t| &*t.ident.id), Some("13"));
+///     }
+///     _ => unreachable!(),
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Operations {
+    /// Information about this implementation of the specification. The version must be
+    /// 1.1 or later. Defaults to version 1.1 with the Atomic Operations extension
+    /// applied when a new document is created with [`Operations::new`] or the member is
+    /// missing from a deserialized document.
+    ///
+    /// [`Operations::new`]: #method.new
+    #[serde(default = "default_jsonapi", with = "serde_jsonapi")]
+    pub jsonapi: JsonApi,
+
+    /// Contains relevant links. If this value of this field is empty, it will not be
+    /// serialized. For more information, check out the *[links]* section of the JSON
+    /// API specification.
+    ///
+    /// [links]: https://goo.gl/E4E6Vt
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub links: Map<Key, Link>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+
+    /// The operations to perform, in order.
+    #[serde(rename = "atomic:operations")]
+    pub operations: Vec<Operation>,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl Operations {
+    /// Returns a new `Operations` document containing `operations`.
+    pub fn new(operations: Vec<Operation>) -> Self {
+        Operations {
+            operations,
+            jsonapi: default_jsonapi(),
+            links: Default::default(),
+            meta: Default::default(),
+            _ext: (),
+        }
+    }
+}
+
+/// A response document containing the result of each operation in an [`Operations`]
+/// document.
+///
+/// [`Operations`]: ./struct.Operations.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Results {
+    /// Information about this implementation of the specification. The version must be
+    /// 1.1 or later. Defaults to version 1.1 with the Atomic Operations extension
+    /// applied when a new document is created with [`Results::new`] or the member is
+    /// missing from a deserialized document.
+    ///
+    /// [`Results::new`]: #method.new
+    #[serde(default = "default_jsonapi", with = "serde_jsonapi")]
+    pub jsonapi: JsonApi,
+
+    /// Contains relevant links. If this value of this field is empty, it will not be
+    /// serialized. For more information, check out the *[links]* section of the JSON
+    /// API specification.
+    ///
+    /// [links]: https://goo.gl/E4E6Vt
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub links: Map<Key, Link>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+
+    /// The result of each operation, in the same order as the operations.
+    #[serde(rename = "atomic:results")]
+    pub results: Vec<OperationResult>,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl Results {
+    /// Returns a new `Results` document containing `results`.
+    pub fn new(results: Vec<OperationResult>) -> Self {
+        Results {
+            results,
+            jsonapi: default_jsonapi(),
+            links: Default::default(),
+            meta: Default::default(),
+            _ext: (),
+        }
+    }
+}
+
+/// The result of a single operation.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OperationResult {
+    /// The resource that was added or updated by the operation. If this value is `None`,
+    /// it will not be serialized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Object>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl OperationResult {
+    /// Returns a new `OperationResult` with the specified `data`.
+    pub fn new(data: Option<Object>) -> Self {
+        OperationResult {
+            data,
+            ..Default::default()
+        }
+    }
+}
+
+/// A single operation.
+///
+/// Each operation targets either a resource or a relationship with a [`Target`] or a
+/// URI. When the target is a relationship, the operation's data is resource linkage.
+/// Otherwise, the data is a resource object.
+///
+/// [`Target`]: ./struct.Target.html
+#[derive(Clone, Debug)]
+pub enum Operation {
+    /// Creates a resource or adds members to a to-many relationship.
+    Add {
+        /// The resource or relationship that the operation targets.
+        target: Option<Target>,
+
+        /// A URI that identifies the target of the operation.
+        href: Option<String>,
+
+        /// The resource to create or the linkage to add.
+        data: OperationData,
+
+        /// Non-standard meta information.
+        meta: Map,
+    },
+
+    /// Updates a resource or replaces the linkage of a relationship.
+    Update {
+        /// The resource or relationship that the operation targets.
+        target: Option<Target>,
+
+        /// A URI that identifies the target of the operation.
+        href: Option<String>,
+
+        /// The updated resource or the replacement linkage.
+        data: OperationData,
+
+        /// Non-standard meta information.
+        meta: Map,
+    },
+
+    /// Deletes a resource or removes members from a to-many relationship.
+    Remove {
+        /// The resource or relationship that the operation targets.
+        target: Option<Target>,
+
+        /// A URI that identifies the target of the operation.
+        href: Option<String>,
+
+        /// The linkage to remove from a to-many relationship.
+        data: Option<OperationData>,
+
+        /// Non-standard meta information.
+        meta: Map,
+    },
+}
+
+impl Operation {
+    /// Returns the resource or relationship that the operation targets.
+    pub fn target(&self) -> Option<&Target> {
+        match *self {
+            Operation::Add { ref target, .. }
+            | Operation::Update { ref target, .. }
+            | Operation::Remove { ref target, .. } => target.as_ref(),
+        }
+    }
+
+    fn parts_mut(&mut self) -> (Option<&mut Target>, Option<&mut OperationData>) {
+        match *self {
+            Operation::Add {
+                ref mut target,
+                ref mut data,
+                ..
+            }
+            | Operation::Update {
+                ref mut target,
+                ref mut data,
+                ..
+            } => (target.as_mut(), Some(data)),
+            Operation::Remove {
+                ref mut target,
+                ref mut data,
+                ..
+            } => (target.as_mut(), data.as_mut()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Operation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Op {
+            Add,
+            Update,
+            Remove,
+        }
+
+        #[derive(Deserialize)]
+        struct OperationRepr {
+            op: Op,
+            #[serde(rename = "ref")]
+            target: Option<Target>,
+            href: Option<String>,
+            #[serde(default, deserialize_with = "present")]
+            data: Option<JsonValue>,
+            #[serde(default)]
+            meta: Map,
+        }
+
+        let OperationRepr {
+            op,
+            target,
+            href,
+            data,
+            meta,
+        } = OperationRepr::deserialize(deserializer)?;
+
+        let is_linkage = match (&target, &data) {
+            (&Some(Target { relationship: Some(_), .. }), _) => true,
+            (_, &Some(JsonValue::Object(_))) => false,
+            _ => true,
+        };
+
+        let data = match data {
+            Some(value) if is_linkage => serde_json::from_value(value)
+                .map(OperationData::Linkage)
+                .map(Some)
+                .map_err(D::Error::custom)?,
+            Some(value) => serde_json::from_value(value)
+                .map(|object| Some(OperationData::Resource(Box::new(object))))
+                .map_err(D::Error::custom)?,
+            None => None,
+        };
+
+        match op {
+            Op::Add => Ok(Operation::Add {
+                target,
+                href,
+                meta,
+                data: data.ok_or_else(|| D::Error::missing_field("data"))?,
+            }),
+            Op::Update => Ok(Operation::Update {
+                target,
+                href,
+                meta,
+                data: data.ok_or_else(|| D::Error::missing_field("data"))?,
+            }),
+            Op::Remove => {
+                if target.is_none() && href.is_none() {
+                    return Err(D::Error::missing_field("ref"));
+                }
+
+                Ok(Operation::Remove {
+                    target,
+                    href,
+                    data,
+                    meta,
+                })
+            }
+        }
+    }
+}
+
+impl Serialize for Operation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Operation", 5)?;
+        let (op, target, href, data, meta) = match *self {
+            Operation::Add {
+                ref target,
+                ref href,
+                ref data,
+                ref meta,
+            } => ("add", target, href, Some(data), meta),
+            Operation::Update {
+                ref target,
+                ref href,
+                ref data,
+                ref meta,
+            } => ("update", target, href, Some(data), meta),
+            Operation::Remove {
+                ref target,
+                ref href,
+                ref data,
+                ref meta,
+            } => ("remove", target, href, data.as_ref(), meta),
+        };
+
+        state.serialize_field("op", op)?;
+
+        if let Some(ref target) = *target {
+            state.serialize_field("ref", target)?;
+        }
+
+        if let Some(ref href) = *href {
+            state.serialize_field("href", href)?;
+        }
+
+        if let Some(data) = data {
+            state.serialize_field("data", data)?;
+        }
+
+        if !meta.is_empty() {
+            state.serialize_field("meta", meta)?;
+        }
+
+        state.end()
+    }
+}
+
+/// The data of an operation.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum OperationData {
+    /// Resource linkage. Used when the operation targets a relationship.
+    Linkage(Data<Identifier>),
+
+    /// A resource object. Used when the operation targets a resource.
+    Resource(Box<NewObject>),
+}
+
+/// References the resource or relationship that an operation targets.
+///
+/// The resource is identified by either an `id` or a `lid`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Target {
+    /// Identifies the targeted resource. The [`id`] of this value is empty if the
+    /// resource is referenced by [`lid`].
+    ///
+    /// [`id`]: ../struct.Identifier.html#structfield.id
+    /// [`lid`]: ../struct.Identifier.html#structfield.lid
+    pub ident: Identifier,
+
+    /// The name of the targeted relationship. If this value is `None`, the operation
+    /// targets the resource itself.
+    pub relationship: Option<Key>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Target {
+    /// Returns a new `Target` that references the resource identified by `ident`.
+    pub fn new(ident: Identifier) -> Self {
+        Target {
+            ident,
+            relationship: None,
+            _ext: (),
+        }
+    }
+
+    /// Returns a new `Target` that references the relationship `name` of the resource
+    /// identified by `ident`.
+    pub fn relationship(ident: Identifier, name: Key) -> Self {
+        Target {
+            ident,
+            relationship: Some(name),
+            _ext: (),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Target {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        struct TargetRepr {
+            id: Option<String>,
+            lid: Option<String>,
+            relationship: Option<Key>,
+            #[serde(rename = "type")]
+            kind: Key,
+        }
+
+        let TargetRepr {
+            id,
+            lid,
+            relationship,
+            kind,
+        } = TargetRepr::deserialize(deserializer)?;
+
+        let ident = match (id, lid) {
+            (Some(id), lid) => {
+                let mut ident = Identifier::new(kind, id);
+
+                ident.lid = lid;
+                ident
+            }
+            (None, Some(lid)) => Identifier::local(kind, lid),
+            (None, None) => return Err(D::Error::missing_field("id")),
+        };
+
+        Ok(Target {
+            ident,
+            relationship,
+            _ext: (),
+        })
+    }
+}
+
+impl Serialize for Target {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let ident = &self.ident;
+        let mut state = serializer.serialize_struct("Target", 4)?;
+
+        state.serialize_field("type", &ident.kind)?;
+
+        if !ident.id.is_empty() {
+            state.serialize_field("id", &ident.id)?;
+        }
+
+        if let Some(ref lid) = ident.lid {
+            state.serialize_field("lid", lid)?;
+        }
+
+        if let Some(ref relationship) = self.relationship {
+            state.serialize_field("relationship", relationship)?;
+        }
+
+        state.end()
+    }
+}
+
+/// A trait to apply the operations of an [`Operations`] document.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::atomic::{Operation, OperationData, OperationResult, Operations,
+///                             Processor};
+/// use json_api::doc::{ErrorObject, NewObject, Object};
+/// use json_api::http::StatusCode;
+///
+/// struct Store(Vec<Object>);
+///
+/// impl Processor for Store {
+///     fn apply(&mut self, op: Operation) -> Result<OperationResult, Vec<ErrorObject>> {
+///         match op {
+///             Operation::Add { data: OperationData::Resource(new), .. } => {
+///                 let id = (self.0.len() + 1).to_string();
+///                 let obj = Object::new(new.kind.clone(), id);
+///
+///                 self.0.push(obj.clone());
+///                 Ok(OperationResult::new(Some(obj)))
+///             }
+///             _ => Err(vec![ErrorObject::new(Some(StatusCode::FORBIDDEN))]),
+///         }
+///     }
+/// }
+///
+/// let mut store = Store(Vec::new());
+/// let add = Operation::Add {
+///     target: None,
+///     href: None,
+///     data: OperationData::Resource(Box::new(NewObject::new("articles".parse()?))),
+///     meta: Default::default(),
+/// };
+///
+/// let results = store.process(Operations::new(vec![add.clone()])).unwrap();
+/// assert_eq!(results.results.len(), 1);
+///
+/// let remove = Operation::Remove {
+///     target: None,
+///     href: Some("/articles/1".to_owned()),
+///     data: None,
+///     meta: Default::default(),
+/// };
+///
+/// match store.process(Operations::new(vec![add, remove])) {
+///     Err(json_api::doc::Document::Err { errors, .. }) => {
+///         let source = errors[0].source.as_ref().unwrap();
+///         assert_eq!(source.pointer, Some("/atomic:operations/1".to_owned()));
+///     }
+///     _ => unreachable!(),
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Operations`]: ./struct.Operations.html
+pub trait Processor {
+    /// Applies a single operation.
+    ///
+    /// Each error pointer that is returned should be relative to the operation (i.e
+    /// `/data/attributes/title`). Pointers are prefixed with the position of the
+    /// operation in the document by [`process`].
+    ///
+    /// [`process`]: #method.process
+    fn apply(&mut self, operation: Operation) -> Result<OperationResult, Vec<ErrorObject>>;
+
+    /// Applies each operation in `doc` in order, stopping at the first failure.
+    ///
+    /// Before an operation is applied, each identifier in the operation that only has a
+    /// `lid` is assigned the `id` of the resource that was created with that `lid` by a
+    /// previous operation.
+    ///
+    /// If an operation fails, an error document is returned. The pointer of each error
+    /// in the document references the operation that failed (i.e
+    /// `/atomic:operations/1`). Implementations that require the operations to be
+    /// atomic are responsible for rolling back the operations that were already applied.
+    fn process(&mut self, doc: Operations) -> Result<Results, Document<Object>> {
+        let mut lids = Map::<(Key, String), String>::new();
+        let mut results = Vec::with_capacity(doc.operations.len());
+
+        for (index, mut operation) in doc.operations.into_iter().enumerate() {
+            resolve(&mut operation, &lids);
+
+            let created = match operation {
+                Operation::Add {
+                    data: OperationData::Resource(ref object),
+                    ..
+                } => object
+                    .lid
+                    .as_ref()
+                    .map(|lid| (object.kind.clone(), lid.clone())),
+                _ => None,
+            };
+
+            match self.apply(operation) {
+                Ok(result) => {
+                    if let (Some(key), Some(object)) = (created, result.data.as_ref()) {
+                        lids.insert(key, object.id.clone());
+                    }
+
+                    results.push(result);
+                }
+                Err(errors) => {
+                    let prefix = format!("/atomic:operations/{}", index);
+                    let errors = errors
+                        .into_iter()
+                        .map(|mut error| {
+                            let source = error.source.get_or_insert_with(Default::default);
+                            let pointer = match source.pointer.take() {
+                                Some(ref pointer) => format!("{}{}", prefix, pointer),
+                                None => prefix.clone(),
+                            };
+
+                            source.pointer = Some(pointer);
+                            error
+                        })
+                        .collect();
+
+                    return Err(Document::Err {
+                        errors,
+                        jsonapi: default_jsonapi(),
+                        links: Default::default(),
+                        meta: Default::default(),
+                    });
+                }
+            }
+        }
+
+        Ok(Results::new(results))
+    }
+}
+
+fn resolve(operation: &mut Operation, lids: &Map<(Key, String), String>) {
+    let resolve_ident = |ident: &mut Identifier| {
+        if !ident.id.is_empty() {
+            return;
+        }
+
+        let id = ident
+            .lid
+            .as_ref()
+            .and_then(|lid| lids.get(&(ident.kind.clone(), lid.clone())));
+
+        if let Some(id) = id {
+            ident.id = id.clone();
+        }
+    };
+
+    let (target, data) = operation.parts_mut();
+
+    if let Some(target) = target {
+        resolve_ident(&mut target.ident);
+    }
+
+    let linkage = match data {
+        Some(&mut OperationData::Linkage(ref mut data)) => vec![data],
+        Some(&mut OperationData::Resource(ref mut object)) => object
+            .relationships
+            .values_mut()
+            .map(|rel| &mut rel.data)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    for data in linkage {
+        match *data {
+            Data::Member(ref mut data) => {
+                if let Some(ref mut ident) = **data {
+                    resolve_ident(ident);
+                }
+            }
+            Data::Collection(ref mut data) => for ident in data {
+                resolve_ident(ident);
+            },
+        }
+    }
+}
+
+/// Deserializes a member that may be `null` as `Some`. Used with `#[serde(default)]` so
+/// that an explicit `null` can be told apart from a member that is absent.
+fn present<'de, D>(deserializer: D) -> Result<Option<JsonValue>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    JsonValue::deserialize(deserializer).map(Some)
+}
+
+fn default_jsonapi() -> JsonApi {
+    let mut jsonapi = JsonApi::new(Version::V1_1);
+
+    jsonapi.ext.push(EXT_URI.parse().unwrap());
+    jsonapi
+}
+
+mod serde_jsonapi {
+    use serde::de::{Deserialize, Deserializer, Error as DeError};
+    use serde::ser::{Error as SerError, Serialize, Serializer};
+
+    use doc::{JsonApi, Version};
+
+    const MESSAGE: &str = "documents that use the atomic operations extension must \
+                           declare version 1.1 of the specification";
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<JsonApi, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = JsonApi::deserialize(deserializer)?;
+
+        if value.version < Version::V1_1 {
+            return Err(D::Error::custom(MESSAGE));
+        }
+
+        Ok(value)
+    }
+
+    pub fn serialize<S>(value: &JsonApi, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if value.version < Version::V1_1 {
+            return Err(S::Error::custom(MESSAGE));
+        }
+
+        value.serialize(serializer)
+    }
+}
+
@@ -0,0 +1,653 @@
+//! A document model that borrows from the JSON text it was deserialized from.
+//!
+//! The types in this module are deserialized without allocating a new `String` for each
+//! id, type, member name, or string value that does not contain an escape sequence.
+//! This is useful when only a small part of a large document is inspected (e.g. the
+//! `type` and `id` of the primary data).
+//!
+//! Unlike the owned types in the [`doc`] module, member names are not converted to
+//! kebab case or checked against the requirements of the specification when they are
+//! deserialized. Any of the types in this module can be converted to its owned
+//! counterpart with the `into_owned` method. The conversion applies the same rules as
+//! deserializing the owned type from JSON text.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate json_api;
+//! #
+//! # use json_api::Error;
+//! #
+//! # fn example() -> Result<(), Error> {
+//! use json_api::doc::{self, borrowed, Object};
+//!
+//! let json = r#"{
+//!     "data": {
+//!         "type": "articles",
+//!         "id": "1",
+//!         "attributes": { "title": "Rails is Omakase" }
+//!     }
+//! }"#;
+//!
+//! let doc = borrowed::from_str(json)?;
+//!
+//! if let borrowed::Document::Ok { ref data, .. } = doc {
+//!     let item = data.iter().next().unwrap();
+//!
+//!     assert_eq!(item.kind, "articles");
+//!     assert_eq!(item.id.as_ref().map(|id| &**id), Some("1"));
+//! }
+//!
+//! let doc: doc::Document<Object> = doc.into_owned()?;
+//! #
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+//!
+//! [`doc`]: ../index.html
+
+use std::borrow::Cow;
+use std::fmt::{self, Formatter};
+use std::marker::PhantomData;
+use std::slice;
+
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{self, Deserialize, DeserializeOwned, Deserializer, MapAccess, SeqAccess,
+                Visitor};
+use serde::ser::{Serialize, Serializer};
+use serde_json;
+
+use doc::{self, PrimaryData};
+use error::Error;
+use value::{self, Map, Number};
+
+/// A borrowed JSON API document. The borrowed counterpart of [`doc::Document`].
+///
+/// [`doc::Document`]: ../enum.Document.html
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Document<'a> {
+    /// Does not contain errors.
+    Ok {
+        /// The primary data of the document.
+        data: Data<Object<'a>>,
+
+        /// Included resources.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        included: Vec<Object<'a>>,
+
+        /// The `jsonapi` object of the document, if present.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        jsonapi: Option<Value<'a>>,
+
+        /// Top-level links.
+        #[serde(skip_serializing_if = "Map::is_empty")]
+        links: Map<Cow<'a, str>, Value<'a>>,
+
+        /// Top-level meta information.
+        #[serde(skip_serializing_if = "Map::is_empty")]
+        meta: Map<Cow<'a, str>, Value<'a>>,
+    },
+
+    /// Contains 1 or more error(s).
+    Err {
+        /// The error objects of the document.
+        errors: Vec<Value<'a>>,
+
+        /// The `jsonapi` object of the document, if present.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        jsonapi: Option<Value<'a>>,
+
+        /// Top-level links.
+        #[serde(skip_serializing_if = "Map::is_empty")]
+        links: Map<Cow<'a, str>, Value<'a>>,
+
+        /// Top-level meta information.
+        #[serde(skip_serializing_if = "Map::is_empty")]
+        meta: Map<Cow<'a, str>, Value<'a>>,
+    },
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Document<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(DocumentVisitor)
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Converts the document to an owned [`doc::Document`].
+    ///
+    /// [`doc::Document`]: ../enum.Document.html
+    pub fn into_owned<T: PrimaryData>(self) -> Result<doc::Document<T>, Error> {
+        into_owned(&self)
+    }
+}
+
+/// The primary data of a borrowed document or the linkage of a borrowed relationship.
+/// The borrowed counterpart of [`doc::Data`].
+///
+/// [`doc::Data`]: ../enum.Data.html
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Data<T> {
+    /// A collection of `T`.
+    Collection(Vec<T>),
+
+    /// An optional `T`.
+    Member(Box<Option<T>>),
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Data<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DataVisitor(PhantomData))
+    }
+}
+
+impl<T> Data<T> {
+    /// Returns an iterator over the items of the collection or the member, if present.
+    pub fn iter<'b>(&'b self) -> slice::Iter<'b, T> {
+        match *self {
+            Data::Collection(ref items) => items.iter(),
+            Data::Member(ref item) => match **item {
+                Some(ref item) => slice::from_ref(item).iter(),
+                None => [].iter(),
+            },
+        }
+    }
+}
+
+/// A borrowed resource identifier object. The borrowed counterpart of
+/// [`doc::Identifier`].
+///
+/// [`doc::Identifier`]: ../struct.Identifier.html
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Identifier<'a> {
+    /// The id of the resource. This field is empty if the identifier references a
+    /// resource by `lid` alone.
+    #[serde(borrow, default, deserialize_with = "cow", skip_serializing_if = "is_empty")]
+    pub id: Cow<'a, str>,
+
+    /// The type of the resource, as it appears in the JSON text.
+    #[serde(borrow, deserialize_with = "cow", rename = "type")]
+    pub kind: Cow<'a, str>,
+
+    /// The local id of the resource, if present.
+    #[serde(borrow, default, deserialize_with = "option_cow")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lid: Option<Cow<'a, str>>,
+
+    /// Non-standard meta information.
+    #[serde(borrow, default, deserialize_with = "map")]
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub meta: Map<Cow<'a, str>, Value<'a>>,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl<'a> Identifier<'a> {
+    /// Converts the identifier to an owned [`doc::Identifier`].
+    ///
+    /// [`doc::Identifier`]: ../struct.Identifier.html
+    pub fn into_owned(self) -> Result<doc::Identifier, Error> {
+        into_owned(&self)
+    }
+}
+
+/// A borrowed resource object. The borrowed counterpart of [`doc::Object`] and
+/// [`doc::NewObject`].
+///
+/// [`doc::NewObject`]: ../struct.NewObject.html
+/// [`doc::Object`]: ../struct.Object.html
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Object<'a> {
+    /// The id of the resource, if present.
+    #[serde(borrow, default, deserialize_with = "option_cow")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Cow<'a, str>>,
+
+    /// The type of the resource, as it appears in the JSON text.
+    #[serde(borrow, deserialize_with = "cow", rename = "type")]
+    pub kind: Cow<'a, str>,
+
+    /// The local id of the resource, if present.
+    #[serde(borrow, default, deserialize_with = "option_cow")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lid: Option<Cow<'a, str>>,
+
+    /// The attributes of the resource.
+    #[serde(borrow, default, deserialize_with = "map")]
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub attributes: Map<Cow<'a, str>, Value<'a>>,
+
+    /// Links related to the resource.
+    #[serde(borrow, default, deserialize_with = "map")]
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub links: Map<Cow<'a, str>, Value<'a>>,
+
+    /// Non-standard meta information.
+    #[serde(borrow, default, deserialize_with = "map")]
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub meta: Map<Cow<'a, str>, Value<'a>>,
+
+    /// The relationships of the resource.
+    #[serde(borrow, default, deserialize_with = "map")]
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub relationships: Map<Cow<'a, str>, Relationship<'a>>,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl<'a> Object<'a> {
+    /// Converts the resource object to an owned type that can be the primary data of a
+    /// document (i.e [`doc::Object`], [`doc::NewObject`], or [`doc::Identifier`]).
+    ///
+    /// [`doc::Identifier`]: ../struct.Identifier.html
+    /// [`doc::NewObject`]: ../struct.NewObject.html
+    /// [`doc::Object`]: ../struct.Object.html
+    pub fn into_owned<T: PrimaryData>(self) -> Result<T, Error> {
+        into_owned(&self)
+    }
+}
+
+/// A borrowed relationship object. The borrowed counterpart of [`doc::Relationship`].
+///
+/// [`doc::Relationship`]: ../struct.Relationship.html
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Relationship<'a> {
+    /// The resource linkage of the relationship.
+    #[serde(borrow)]
+    pub data: Data<Identifier<'a>>,
+
+    /// Links related to the relationship.
+    #[serde(borrow, default, deserialize_with = "map")]
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub links: Map<Cow<'a, str>, Value<'a>>,
+
+    /// Non-standard meta information.
+    #[serde(borrow, default, deserialize_with = "map")]
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub meta: Map<Cow<'a, str>, Value<'a>>,
+
+    /// Private field for backwards compatibility.
+    #[serde(skip)]
+    _ext: (),
+}
+
+impl<'a> Relationship<'a> {
+    /// Converts the relationship to an owned [`doc::Relationship`].
+    ///
+    /// [`doc::Relationship`]: ../struct.Relationship.html
+    pub fn into_owned(self) -> Result<doc::Relationship, Error> {
+        into_owned(&self)
+    }
+}
+
+/// A borrowed JSON value. The borrowed counterpart of [`Value`].
+///
+/// [`Value`]: ../../value/enum.Value.html
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    /// A null value.
+    Null,
+
+    /// An array of values.
+    Array(Vec<Value<'a>>),
+
+    /// A boolean value.
+    Bool(bool),
+
+    /// An integer or floating point value.
+    Number(Number),
+
+    /// A JSON object. Keys appear as they do in the JSON text.
+    Object(Map<Cow<'a, str>, Value<'a>>),
+
+    /// A string value.
+    String(Cow<'a, str>),
+}
+
+impl<'a> Value<'a> {
+    /// Converts the value to an owned [`Value`]. Fails if the value contains an object
+    /// with a key that is not a valid member name.
+    ///
+    /// [`Value`]: ../../value/enum.Value.html
+    pub fn into_owned(self) -> Result<value::Value, Error> {
+        value::to_value(&self)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Value<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor;
+
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = Value<'de>;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("any valid JSON API value")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(Value::Bool(value))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(Value::Number(value.into()))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(Value::Number(value.into()))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(Number::from_f64(value).map_or(Value::Null, Value::Number))
+            }
+
+            fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Value::String(Cow::Borrowed(value)))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(Value::String(Cow::Owned(value.to_owned())))
+            }
+
+            fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+                Ok(Value::String(Cow::Owned(value)))
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                Deserialize::deserialize(deserializer)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut array = Vec::with_capacity(access.size_hint().unwrap_or(0));
+
+                while let Some(value) = access.next_element()? {
+                    array.push(value);
+                }
+
+                Ok(Value::Array(array))
+            }
+
+            fn visit_map<A>(self, access: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                MapVisitor::default().visit_map(access).map(Value::Object)
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+impl<'a> Serialize for Value<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            Value::Null => serializer.serialize_none(),
+            Value::Array(ref value) => value.serialize(serializer),
+            Value::Bool(value) => serializer.serialize_bool(value),
+            Value::Number(ref value) => value.serialize(serializer),
+            Value::Object(ref value) => value.serialize(serializer),
+            Value::String(ref value) => serializer.serialize_str(value),
+        }
+    }
+}
+
+/// Deserialize a borrowed document from a string of JSON text.
+pub fn from_str<'a>(data: &'a str) -> Result<Document<'a>, Error> {
+    Ok(serde_json::from_str(data)?)
+}
+
+/// Deserialize a borrowed document from bytes of JSON text.
+pub fn from_slice<'a>(data: &'a [u8]) -> Result<Document<'a>, Error> {
+    Ok(serde_json::from_slice(data)?)
+}
+
+fn into_owned<T: Serialize, U: DeserializeOwned>(value: &T) -> Result<U, Error> {
+    Ok(serde_json::from_value(serde_json::to_value(value)?)?)
+}
+
+/// Dispatches on the `data` and `errors` members of a document without buffering the
+/// rest of the document.
+struct DocumentVisitor;
+
+impl<'de> Visitor<'de> for DocumentVisitor {
+    type Value = Document<'de>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a JSON API document")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut data = None;
+        let mut errors = None;
+        let mut included = None;
+        let mut jsonapi = None;
+        let mut links = None;
+        let mut meta = None;
+
+        while let Some(Str(key)) = access.next_key()? {
+            match &*key {
+                "data" => data = Some(access.next_value()?),
+                "errors" => errors = Some(access.next_value()?),
+                "included" => included = Some(access.next_value()?),
+                "jsonapi" => jsonapi = access.next_value()?,
+                "links" => links = Some(access.next_value::<Members<_>>()?.0),
+                "meta" => meta = Some(access.next_value::<Members<_>>()?.0),
+                _ => {
+                    access.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        let links = links.unwrap_or_default();
+        let meta = meta.unwrap_or_default();
+
+        match (data, errors) {
+            (Some(data), None) => Ok(Document::Ok {
+                data,
+                jsonapi,
+                links,
+                meta,
+                included: included.unwrap_or_default(),
+            }),
+            (None, Some(errors)) => Ok(Document::Err {
+                errors,
+                jsonapi,
+                links,
+                meta,
+            }),
+            (Some(_), Some(_)) => Err(de::Error::custom(
+                "the members `data` and `errors` must not coexist in a document",
+            )),
+            (None, None) => Err(de::Error::missing_field("data")),
+        }
+    }
+}
+
+/// Deserializes a `null`, a single `T`, or an array of `T` without buffering the input.
+struct DataVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for DataVisitor<T> {
+    type Value = Data<T>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("null, a JSON object, or an array of JSON objects")
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Data::Member(Box::new(None)))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Data::Member(Box::new(None)))
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut items = Vec::with_capacity(access.size_hint().unwrap_or(0));
+
+        while let Some(item) = access.next_element()? {
+            items.push(item);
+        }
+
+        Ok(Data::Collection(items))
+    }
+
+    fn visit_map<A>(self, access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let item = T::deserialize(MapAccessDeserializer::new(access))?;
+        Ok(Data::Member(Box::new(Some(item))))
+    }
+}
+
+struct CowVisitor;
+
+impl<'de> Visitor<'de> for CowVisitor {
+    type Value = Cow<'de, str>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, value: &'de str) -> Result<Self::Value, E> {
+        Ok(Cow::Borrowed(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(value.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(value))
+    }
+}
+
+struct Str<'a>(Cow<'a, str>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for Str<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(CowVisitor).map(Str)
+    }
+}
+
+struct Members<'a, V>(Map<Cow<'a, str>, V>);
+
+impl<'de: 'a, 'a, V: Deserialize<'de>> Deserialize<'de> for Members<'a, V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        map(deserializer).map(Members)
+    }
+}
+
+struct MapVisitor<V>(PhantomData<V>);
+
+impl<V> Default for MapVisitor<V> {
+    fn default() -> Self {
+        MapVisitor(Default::default())
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Visitor<'de> for MapVisitor<V> {
+    type Value = Map<Cow<'de, str>, V>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a JSON object")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut map = Map::with_capacity(access.size_hint().unwrap_or(0));
+
+        while let Some((Str(key), value)) = access.next_entry()? {
+            map.insert(key, value);
+        }
+
+        Ok(map)
+    }
+}
+
+fn is_empty(value: &Cow<str>) -> bool {
+    value.is_empty()
+}
+
+fn cow<'de, D>(deserializer: D) -> Result<Cow<'de, str>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(CowVisitor)
+}
+
+fn option_cow<'de, D>(deserializer: D) -> Result<Option<Cow<'de, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<Str>::deserialize(deserializer).map(|value| value.map(|Str(value)| value))
+}
+
+fn map<'de, D, V>(deserializer: D) -> Result<Map<Cow<'de, str>, V>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    deserializer.deserialize_map(MapVisitor::default())
+}
@@ -0,0 +1,182 @@
+use std::mem;
+
+use doc::{Data, Document, ErrorObject, JsonApi, Link, PrimaryData};
+use error::Error;
+use query::Query;
+use value::{Key, Map, Value};
+use view::Render;
+
+/// An implementation of the "builder pattern" that can be used to construct a
+/// new document.
+///
+/// Top-level links and meta information that are supplied to the builder are merged
+/// with the links and meta information of the rendered primary data. If a key is
+/// present in both, the value supplied to the builder is used.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// struct Post(u64);
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.0;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{Document, Object};
+///
+/// let posts = vec![Post(1), Post(2)];
+/// let doc: Document<Object> = Document::builder()
+///     .data(&posts[..], None)
+///     .link("self", "https://example.com/posts".parse()?)
+///     .meta("total", 2)
+///     .build()?;
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+pub struct DocumentBuilder<T: PrimaryData> {
+    data: Option<Result<Document<T>, Error>>,
+    errors: Vec<ErrorObject>,
+    jsonapi: Option<JsonApi>,
+    links: Vec<(String, Link)>,
+    meta: Vec<(String, Value)>,
+}
+
+impl<T: PrimaryData> DocumentBuilder<T> {
+    /// Attempt to construct a new document from the previously supplied values.
+    ///
+    /// If one or more error(s) were supplied, a `Document::Err` is returned. An error
+    /// is returned if both primary data and errors were supplied, if the primary data
+    /// could not be rendered, or if a key is not a valid member name.
+    pub fn build(&mut self) -> Result<Document<T>, Error> {
+        let links = self.links
+            .drain(..)
+            .map(|(key, value)| Ok((key.parse()?, value)))
+            .collect::<Result<Map<Key, Link>, Error>>()?;
+
+        let meta = self.meta
+            .drain(..)
+            .map(|(key, value)| Ok((key.parse()?, value)))
+            .collect::<Result<Map, Error>>()?;
+
+        let jsonapi = mem::replace(&mut self.jsonapi, None);
+        let errors = mem::replace(&mut self.errors, Vec::new());
+        let data = mem::replace(&mut self.data, None);
+
+        let mut doc = match (data, errors.is_empty()) {
+            (Some(_), false) => bail!("A document cannot contain both data and errors"),
+            (Some(data), true) => data?,
+            (None, false) => Document::Err {
+                errors,
+                jsonapi: Default::default(),
+                links: Default::default(),
+                meta: Default::default(),
+            },
+            (None, true) => Document::Ok {
+                data: Data::Member(Box::new(None)),
+                included: Default::default(),
+                jsonapi: Default::default(),
+                links: Default::default(),
+                meta: Default::default(),
+            },
+        };
+
+        match doc {
+            Document::Ok {
+                jsonapi: ref mut doc_jsonapi,
+                links: ref mut doc_links,
+                meta: ref mut doc_meta,
+                ..
+            }
+            | Document::Err {
+                jsonapi: ref mut doc_jsonapi,
+                links: ref mut doc_links,
+                meta: ref mut doc_meta,
+                ..
+            } => {
+                if let Some(jsonapi) = jsonapi {
+                    *doc_jsonapi = jsonapi;
+                }
+
+                doc_links.extend(links);
+                doc_meta.extend(meta);
+            }
+        }
+
+        Ok(doc)
+    }
+
+    /// Render `value` as the primary data of the document. The optional `query`
+    /// argument is used to match the field-sets and included resources of the
+    /// document with what is present in the query.
+    pub fn data<R>(&mut self, value: R, query: Option<&Query>) -> &mut Self
+    where
+        R: Render<T>,
+    {
+        self.data = Some(value.render(query));
+        self
+    }
+
+    /// Add an error object to the document.
+    pub fn error(&mut self, error: ErrorObject) -> &mut Self {
+        self.errors.push(error);
+        self
+    }
+
+    /// Add each error object in `iter` to the document.
+    pub fn errors<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = ErrorObject>,
+    {
+        self.errors.extend(iter);
+        self
+    }
+
+    /// Use `value` as the document's `jsonapi` object.
+    pub fn jsonapi(&mut self, value: JsonApi) -> &mut Self {
+        self.jsonapi = Some(value);
+        self
+    }
+
+    /// Add a top-level link to the document.
+    pub fn link<K>(&mut self, key: K, value: Link) -> &mut Self
+    where
+        K: Into<String>,
+    {
+        self.links.push((key.into(), value));
+        self
+    }
+
+    /// Add top-level meta information to the document.
+    pub fn meta<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        self.meta.push((key.into(), value.into()));
+        self
+    }
+}
+
+impl<T: PrimaryData> Default for DocumentBuilder<T> {
+    fn default() -> Self {
+        DocumentBuilder {
+            data: None,
+            errors: Vec::new(),
+            jsonapi: None,
+            links: Vec::new(),
+            meta: Vec::new(),
+        }
+    }
+}
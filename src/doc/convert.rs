@@ -1,33 +1,326 @@
+use std::cell::RefCell;
 use std::io::{Read, Write};
 
-use serde::de::DeserializeOwned;
-use serde_json;
+use serde::de::{Deserialize, DeserializeOwned};
+use serde::ser::{self, Serialize, SerializeSeq, SerializeStruct, Serializer};
+use serde_json::{self, Value as JsonValue};
 
-use doc::{Data, Document, PrimaryData};
+use doc::{pointer, Data, Document, ErrorObject, Identifier, JsonApi, Link, Object,
+          PrimaryData, Version};
 use error::Error;
 use query::Query;
-use value::{self, Value};
-use view::Render;
+use resource::Resource;
+use value::{self, Key, Map, Set, Value};
+use value::fields::Case;
+use view::{Context, Render};
+
+/// Controls how included resources are embedded when a document is interpreted as a
+/// type `U`.
+///
+/// A relationship that references an included resource is replaced with the flattened
+/// attributes and relationships of that resource. This is repeated for the
+/// relationships of the embedded resource until [`max_depth`] is reached. A resource
+/// that is not included in the document, or that would be embedded beyond
+/// [`max_depth`], is represented by its id.
+///
+/// [`max_depth`]: #structfield.max_depth
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Flatten {
+    /// The value that is used in place of a resource that is already being embedded by
+    /// one of its ancestors (i.e article → author → articles). Defaults to
+    /// [`Cycle::Id`].
+    ///
+    /// [`Cycle::Id`]: ./enum.Cycle.html#variant.Id
+    pub cycle: Cycle,
+
+    /// The maximum number of relationships that are followed from the primary data. If
+    /// the value of this field is `None`, included resources are embedded until a cycle
+    /// is detected. Defaults to `None`.
+    pub max_depth: Option<usize>,
+
+    /// The casing convention that member names are converted to before they are
+    /// interpreted as the fields of `U`. Use [`Case::Snake`] to deserialize into a
+    /// struct without renaming its fields. If the value of this field is `None`, member
+    /// names are left as is. Defaults to `None`.
+    ///
+    /// [`Case::Snake`]: ../value/fields/enum.Case.html#variant.Snake
+    pub rename: Option<Case>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Flatten {
+    /// Returns a new `Flatten` with the default options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use json_api::doc::{Cycle, Flatten};
+    /// use json_api::value::fields::Case;
+    ///
+    /// let mut options = Flatten::new();
+    ///
+    /// options.cycle = Cycle::Null;
+    /// options.max_depth = Some(2);
+    /// options.rename = Some(Case::Snake);
+    /// ```
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Default for Flatten {
+    fn default() -> Self {
+        Flatten {
+            cycle: Default::default(),
+            max_depth: None,
+            rename: None,
+            _ext: (),
+        }
+    }
+}
+
+/// The value that is used in place of a resource that would otherwise be embedded in
+/// itself.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Cycle {
+    /// Use the id of the resource.
+    Id,
+
+    /// Use `null`. To-many relationships will contain `null` in place of the resource.
+    Null,
+}
+
+impl Default for Cycle {
+    fn default() -> Self {
+        Cycle::Id
+    }
+}
+
+/// Keeps track of the resources that are being embedded while a document is
+/// flattened.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Flattener<'a> {
+    depth: usize,
+    included: &'a Set<Object>,
+    members: Vec<Map<Key, &'static str>>,
+    options: Flatten,
+    stack: Vec<(Key, String)>,
+}
+
+impl<'a> Flattener<'a> {
+    fn new(included: &'a Set<Object>, options: Flatten) -> Self {
+        Flattener {
+            depth: 0,
+            included,
+            members: Vec::new(),
+            options,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Records the member of the primary data that a flattened field was taken from.
+    /// The `section` is the name of the member that contains it (i.e `attributes`).
+    pub(crate) fn member(&mut self, key: &Key, section: &'static str) {
+        if self.depth == 0 {
+            if let Some(members) = self.members.last_mut() {
+                members.insert(key.clone(), section);
+            }
+        }
+    }
+
+    /// Returns a JSON pointer to the member of the document that a path in the
+    /// flattened value was taken from.
+    fn pointer(&self, path: &[String], is_collection: bool) -> String {
+        let (mut pointer, members, path) = match path.split_first() {
+            Some((index, rest)) if is_collection => {
+                let idx = index.parse::<usize>().ok();
+                let members = idx.and_then(|idx| self.members.get(idx));
+                (format!("/data/{}", index), members, rest)
+            }
+            _ => ("/data".to_owned(), self.members.first(), path),
+        };
+
+        let rename = self.options.rename;
+        let found = path.split_first().and_then(|(field, rest)| {
+            members?
+                .iter()
+                .find(|&(key, _)| match rename {
+                    Some(case) => case.parse(key).ok().map_or(false, |key| key == *field),
+                    None => key == field,
+                })
+                .map(|(key, section)| (key, *section, rest))
+        });
+
+        match found {
+            Some((key, "", _)) => {
+                pointer.push_str(&pointer::to_string(&[key]));
+            }
+            Some((key, "relationships", _)) => {
+                pointer.push_str(&pointer::to_string(&["relationships", key]));
+            }
+            Some((key, section, rest)) => {
+                pointer.push_str(&pointer::to_string(&[section, key]));
+                pointer.push_str(&pointer::to_string(rest));
+            }
+            None => {}
+        }
+
+        pointer
+    }
+
+    pub(crate) fn enter(&mut self, kind: &Key, id: &str) {
+        self.stack.push((kind.clone(), id.to_owned()));
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.stack.pop();
+    }
+
+    pub(crate) fn embed(&mut self, ident: Identifier) -> Value {
+        let is_cycle = self.stack
+            .iter()
+            .any(|&(ref kind, ref id)| *kind == ident.kind && *id == ident.id);
+
+        if is_cycle {
+            return match self.options.cycle {
+                Cycle::Id => ident.id.into(),
+                Cycle::Null => Value::Null,
+            };
+        }
+
+        if self.options.max_depth.map_or(false, |max| self.stack.len() > max) {
+            return ident.id.into();
+        }
+
+        let included = self.included;
+
+        match included.into_iter().find(|item| ident == **item) {
+            Some(item) => {
+                self.depth += 1;
+                let value = item.clone().flatten(self);
+                self.depth -= 1;
+                value
+            }
+            None => ident.id.into(),
+        }
+    }
+}
 
 /// Interpret a `Document<T>` as a type `U`.
+///
+/// Included resources are embedded with the default [`Flatten`] options. Use
+/// [`from_doc_with`] to change them.
+///
+/// [`Flatten`]: ./struct.Flatten.html
+/// [`from_doc_with`]: ./fn.from_doc_with.html
 pub fn from_doc<T, U>(doc: Document<T>) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_doc_with(doc, Flatten::new())
+}
+
+/// Interpret a `Document<T>` as a type `U`, embedding included resources as described
+/// by `options`.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # #[macro_use]
+/// # extern crate serde_derive;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{self, Cycle, Flatten, Object};
+///
+/// #[derive(Deserialize)]
+/// struct Article {
+///     title: String,
+///     author: Person,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Person {
+///     name: String,
+///     articles: Vec<Option<String>>,
+/// }
+///
+/// let mut options = Flatten::new();
+/// options.cycle = Cycle::Null;
+///
+/// let article: Article = doc::from_str_with::<Object, _>(r#"{
+///     "data": {
+///         "type": "articles",
+///         "id": "1",
+///         "attributes": { "title": "Rails is Omakase" },
+///         "relationships": {
+///             "author": { "data": { "type": "people", "id": "9" } }
+///         }
+///     },
+///     "included": [{
+///         "type": "people",
+///         "id": "9",
+///         "attributes": { "name": "Dan Gebhardt" },
+///         "relationships": {
+///             "articles": { "data": [{ "type": "articles", "id": "1" }] }
+///         }
+///     }]
+/// }"#, options)?;
+///
+/// assert_eq!(article.author.name, "Dan Gebhardt");
+/// assert_eq!(article.author.articles, vec![None]);
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+pub fn from_doc_with<T, U>(doc: Document<T>, options: Flatten) -> Result<U, Error>
 where
     T: PrimaryData,
     U: DeserializeOwned,
 {
     match doc {
         Document::Ok { data, included, .. } => {
-            let value = value::convert::to_json(match data {
+            let mut state = Flattener::new(&included, options);
+            let is_collection = match data {
+                Data::Collection(_) => true,
+                Data::Member(_) => false,
+            };
+            let mut value = match data {
                 Data::Member(data) => match *data {
-                    Some(item) => item.flatten(&included),
+                    Some(item) => {
+                        state.members.push(Map::new());
+                        item.flatten(&mut state)
+                    }
                     None => Value::Null,
                 },
                 Data::Collection(data) => data.into_iter()
-                    .map(|item| item.flatten(&included))
+                    .map(|item| {
+                        state.members.push(Map::new());
+                        item.flatten(&mut state)
+                    })
                     .collect(),
-            });
+            };
+
+            if let Some(case) = options.rename {
+                value = rename(value, case)?;
+            }
 
-            Ok(serde_json::from_value(value)?)
+            let value = value::convert::to_json(value);
+
+            pointer::from_value(&value).map_err(|(path, e)| {
+                let pointer = state.pointer(&path, is_collection);
+                Error::invalid_document(&pointer, &e.to_string())
+            })
         }
         Document::Err { .. } => {
             let e = Error::from("Document contains one or more error(s)");
@@ -44,7 +337,19 @@ where
     T: PrimaryData,
     U: DeserializeOwned,
 {
-    from_doc::<T, _>(serde_json::from_reader(data)?)
+    from_doc_with(parse::<T>(&read(data)?)?, Flatten::new())
+}
+
+/// Deserialize a `Document<T>` from an IO stream of JSON text and then
+/// iterpret it as a type `U`, embedding included resources as described by
+/// `options`.
+pub fn from_reader_with<R, T, U>(data: R, options: Flatten) -> Result<U, Error>
+where
+    R: Read,
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_doc_with(parse::<T>(&read(data)?)?, options)
 }
 
 /// Deserialize a `Document<T>` from bytes of JSON text and then iterpret it as
@@ -54,7 +359,17 @@ where
     T: PrimaryData,
     U: DeserializeOwned,
 {
-    from_doc::<T, _>(serde_json::from_slice(data)?)
+    from_doc_with(parse::<T>(data)?, Flatten::new())
+}
+
+/// Deserialize a `Document<T>` from bytes of JSON text and then iterpret it as
+/// a type `U`, embedding included resources as described by `options`.
+pub fn from_slice_with<T, U>(data: &[u8], options: Flatten) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_doc_with(parse::<T>(data)?, options)
 }
 
 /// Deserialize a `Document<T>` from a string of JSON text and then iterpret it
@@ -64,7 +379,75 @@ where
     T: PrimaryData,
     U: DeserializeOwned,
 {
-    from_doc::<T, _>(serde_json::from_str(data)?)
+    from_doc_with(parse::<T>(data.as_bytes())?, Flatten::new())
+}
+
+/// Deserialize a `Document<T>` from a string of JSON text and then iterpret it
+/// as a type `U`, embedding included resources as described by `options`.
+pub fn from_str_with<T, U>(data: &str, options: Flatten) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_doc_with(parse::<T>(data.as_bytes())?, options)
+}
+
+/// Deserialize a `Document<T>` from bytes of JSON text. The text is only parsed again
+/// as a `serde_json::Value` if the document is invalid, to locate the member that
+/// caused the failure.
+pub(crate) fn parse<T: PrimaryData>(data: &[u8]) -> Result<Document<T>, Error> {
+    serde_json::from_slice(data).or_else(|e: serde_json::Error| {
+        if !e.is_data() {
+            return Err(e.into());
+        }
+
+        let value = serde_json::from_slice(data)?;
+        Err(locate::<T>(&value, &e))
+    })
+}
+
+/// Reads an IO stream of JSON text to the end so it can be parsed again on failure.
+pub(crate) fn read<R: Read>(mut data: R) -> Result<Vec<u8>, Error> {
+    let mut buf = Vec::new();
+
+    data.read_to_end(&mut buf).map_err(serde_json::Error::io)?;
+    Ok(buf)
+}
+
+/// Returns an error with a JSON pointer to the member of `value` that caused a
+/// `Document<T>` to fail to deserialize.
+fn locate<T: PrimaryData>(value: &JsonValue, e: &serde_json::Error) -> Error {
+    fn check<'de, V>(value: Option<&'de JsonValue>, base: &str) -> Option<Error>
+    where
+        V: Deserialize<'de>,
+    {
+        let (path, e) = pointer::from_value::<V>(value?).err()?;
+        let pointer = format!("{}{}", base, pointer::to_string(path));
+
+        Some(Error::invalid_document(&pointer, &e.to_string()))
+    }
+
+    let top = match value.as_object() {
+        Some(top) => top,
+        None => return Error::invalid_document("", &e.to_string()),
+    };
+
+    let data = match top.get("data") {
+        Some(&JsonValue::Array(ref items)) => items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| check::<T>(Some(item), &format!("/data/{}", idx)))
+            .next(),
+        Some(&JsonValue::Null) => None,
+        item => check::<T>(item, "/data"),
+    };
+
+    data.or_else(|| check::<Set<Object>>(top.get("included"), "/included"))
+        .or_else(|| check::<Vec<ErrorObject>>(top.get("errors"), "/errors"))
+        .or_else(|| check::<JsonApi>(top.get("jsonapi"), "/jsonapi"))
+        .or_else(|| check::<Map<Key, Link>>(top.get("links"), "/links"))
+        .or_else(|| check::<Map>(top.get("meta"), "/meta"))
+        .unwrap_or_else(|| Error::invalid_document("", &e.to_string()))
 }
 
 /// Render type `T` as a `Document<U>`.
@@ -139,3 +522,220 @@ where
     serde_json::to_writer_pretty(writer, &to_doc(value, query)?)?;
     Ok(())
 }
+
+/// Render each resource yielded by `iter` and serialize the resulting collection
+/// document as JSON into the IO stream.
+///
+/// Unlike [`to_writer`], the resources are not collected before they are written. Each
+/// item of the primary data is rendered and written as soon as it is yielded by `iter`.
+/// Included resources are accumulated and written after the primary data. The output
+/// is identical to the output of [`to_writer`] when called with a slice of the same
+/// resources.
+///
+/// Since the document is written incrementally, consider wrapping `writer` in a
+/// [`BufWriter`]. If an error occurs, `writer` may contain an incomplete document.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// struct Post(u64);
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.0;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Object;
+///
+/// let posts = (1..4).map(Post);
+/// let mut buf = Vec::new();
+///
+/// json_api::doc::to_writer_iter(&mut buf, posts, None)?;
+///
+/// let posts = (1..4).map(Post).collect::<Vec<_>>();
+/// assert_eq!(buf, json_api::to_vec::<_, Object>(&posts[..], None)?);
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`to_writer`]: ./fn.to_writer.html
+/// [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+pub fn to_writer_iter<W, I>(writer: W, iter: I, query: Option<&Query>) -> Result<(), Error>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Resource,
+{
+    let stream = Stream::new(iter, query);
+    let result = serde_json::to_writer(writer, &stream);
+
+    stream.finish(result)
+}
+
+/// Render each resource yielded by `iter` and serialize the resulting collection
+/// document as pretty-printed JSON into the IO stream.
+///
+/// See [`to_writer_iter`] for more information.
+///
+/// [`to_writer_iter`]: ./fn.to_writer_iter.html
+pub fn to_writer_iter_pretty<W, I>(writer: W, iter: I, query: Option<&Query>) -> Result<(), Error>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Resource,
+{
+    let stream = Stream::new(iter, query);
+    let result = serde_json::to_writer_pretty(writer, &stream);
+
+    stream.finish(result)
+}
+
+/// A collection document that renders its primary data while it is serialized.
+struct Stream<'a, I: IntoIterator> {
+    error: RefCell<Option<Error>>,
+    included: RefCell<Set<Object>>,
+    iter: RefCell<Option<I::IntoIter>>,
+    query: Option<&'a Query>,
+}
+
+impl<'a, I> Stream<'a, I>
+where
+    I: IntoIterator,
+    I::Item: Resource,
+{
+    fn new(iter: I, query: Option<&'a Query>) -> Self {
+        Stream {
+            query,
+            error: Default::default(),
+            included: Default::default(),
+            iter: RefCell::new(Some(iter.into_iter())),
+        }
+    }
+
+    fn finish(self, result: Result<(), serde_json::Error>) -> Result<(), Error> {
+        match self.error.into_inner() {
+            Some(e) => Err(e),
+            None => Ok(result?),
+        }
+    }
+
+    fn render(&self) -> Result<Option<Object>, Error> {
+        let item = match self.iter.borrow_mut().as_mut().and_then(Iterator::next) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        let mut included = self.included.borrow_mut();
+        let mut ctx = Context::new(I::Item::kind(), self.query, &mut included);
+        let object = item.to_object(&mut ctx)?;
+
+        check_version(object.required_version())?;
+        Ok(Some(object))
+    }
+
+    fn fail<E: ser::Error>(&self, e: Error) -> E {
+        let message = e.to_string();
+
+        *self.error.borrow_mut() = Some(e);
+        E::custom(message)
+    }
+}
+
+impl<'a, I> Serialize for Stream<'a, I>
+where
+    I: IntoIterator,
+    I::Item: Resource,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        struct StreamData<'s, 'a: 's, I: IntoIterator + 's>(&'s Stream<'a, I>);
+
+        impl<'s, 'a, I> Serialize for StreamData<'s, 'a, I>
+        where
+            I: IntoIterator,
+            I::Item: Resource,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut state = serializer.serialize_seq(None)?;
+
+                loop {
+                    match self.0.render() {
+                        Ok(Some(object)) => state.serialize_element(&object)?,
+                        Ok(None) => break,
+                        Err(e) => return Err(self.0.fail(e)),
+                    }
+                }
+
+                state.end()
+            }
+        }
+
+        let mut state = serializer.serialize_struct("Document", 5)?;
+
+        state.serialize_field("data", &StreamData(self))?;
+
+        {
+            let included = self.included.borrow();
+            let required = included.iter().map(Object::required_version).max();
+
+            if let Err(e) = check_version(required.unwrap_or_default()) {
+                return Err(self.fail(e));
+            }
+
+            if !included.is_empty() {
+                state.serialize_field("included", &*included)?;
+            }
+        }
+
+        state.serialize_field("jsonapi", &JsonApi::default())?;
+        state.end()
+    }
+}
+
+fn check_version(required: Version) -> Result<(), Error> {
+    let declared = JsonApi::default().version;
+
+    if required > declared {
+        let e = Error::from(format!(
+            "document contains members from version {} of the specification \
+             but declares version {}",
+            required, declared
+        ));
+
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+fn rename(value: Value, case: Case) -> Result<Value, Error> {
+    Ok(match value {
+        Value::Array(data) => Value::Array(
+            data.into_iter()
+                .map(|item| rename(item, case))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(data) => Value::Object(
+            data.into_iter()
+                .map(|(key, item)| Ok((case.parse(&key)?, rename(item, case)?)))
+                .collect::<Result<_, Error>>()?,
+        ),
+        value => value,
+    })
+}
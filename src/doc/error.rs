@@ -1,4 +1,5 @@
 use doc::Link;
+use error::{Error, ErrorKind};
 use http::StatusCode;
 use value::{Key, Map};
 
@@ -71,6 +72,84 @@ impl ErrorObject {
     }
 }
 
+impl<'a> From<&'a Error> for ErrorObject {
+    /// Returns an `ErrorObject` that describes `error`.
+    ///
+    /// Errors that are caused by an invalid request, such as a malformed document or
+    /// query string, have the status `400 Bad Request` and a detail that explains the
+    /// problem. If the location of the problem is known, it is included as a JSON
+    /// pointer or the name of a query parameter in the source of the error object.
+    /// Any other error has the status `500 Internal Server Error` and does not contain
+    /// a detail.
+    ///
+    /// An invalid member name in a request document or query string is reported as an
+    /// invalid document or query. A member name that is parsed by the server itself,
+    /// such as a key in a [`resource!`] invocation, is a `500 Internal Server Error`.
+    ///
+    /// [`resource!`]: ../macro.resource.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::doc::{ErrorObject, NewObject};
+    /// use json_api::http::StatusCode;
+    /// use json_api::value::Map;
+    ///
+    /// let e = json_api::from_str::<NewObject, Map>(r#"{
+    ///     "data": {
+    ///         "type": "articles",
+    ///         "attributes": { "first.name": "Dan" }
+    ///     }
+    /// }"#).unwrap_err();
+    ///
+    /// let error = ErrorObject::from(&e);
+    /// let source = error.source.unwrap();
+    ///
+    /// assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+    /// assert_eq!(source.pointer, Some("/data/attributes/first.name".to_owned()));
+    /// # }
+    /// ```
+    fn from(error: &'a Error) -> Self {
+        let (title, source) = match *error.kind() {
+            ErrorKind::IncludeNotAllowed(_)
+            | ErrorKind::IncludeTooDeep(..)
+            | ErrorKind::TooManyIncluded(_) => (
+                "Unsupported Include",
+                Some(ErrorSource::new(Some("include".to_owned()), None)),
+            ),
+            ErrorKind::InvalidDocument(ref value, _) => (
+                "Invalid Document",
+                Some(ErrorSource::new(None, Some(value.clone()))),
+            ),
+            ErrorKind::InvalidParameter(ref value, _) => (
+                "Invalid Query Parameter",
+                Some(ErrorSource::new(Some(value.clone()), None)),
+            ),
+            ErrorKind::Json(ref e) if e.is_syntax() || e.is_eof() => {
+                ("Invalid JSON", None)
+            }
+            ErrorKind::Query(_) => ("Invalid Query", None),
+            ErrorKind::UnsupportedVersion(_) => ("Unsupported Version", None),
+            _ => return ErrorObject::new(Some(StatusCode::INTERNAL_SERVER_ERROR)),
+        };
+
+        let mut object = ErrorObject::new(Some(StatusCode::BAD_REQUEST));
+
+        object.detail = Some(match *error.kind() {
+            ErrorKind::InvalidDocument(_, ref detail)
+            | ErrorKind::InvalidParameter(_, ref detail) => detail.clone(),
+            ref kind => kind.to_string(),
+        });
+
+        object.source = source;
+        object.title = Some(title.to_owned());
+        object
+    }
+}
+
 /// References to the source of the error.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ErrorSource {
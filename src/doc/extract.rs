@@ -0,0 +1,391 @@
+use std::fmt::Display;
+use std::io::Read;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde_json::{self, Value as JsonValue};
+
+use doc::{convert, pointer, Data, Document, Identifier, NewObject, Object, PrimaryData,
+          Relationship};
+use error::Error;
+use value::{self, Key, Map};
+
+/// A trait indicating that a type can be created from a resource object.
+///
+/// This is the inverse of the [`Resource`] trait. Unlike [`from_doc`], the attributes and
+/// relationships of the object are kept apart, and the type of each related resource is
+/// checked. A failure is reported as an error with a JSON pointer to the member of the
+/// document that caused it.
+///
+/// The [json-api-derive] crate provides `#[derive(FromObject)]`. The trait can also be
+/// implemented with an [`Extractor`].
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{self, Extractor, FromObject, Object};
+///
+/// struct Post {
+///     id: u64,
+///     title: String,
+///     author: Option<u64>,
+/// }
+///
+/// impl FromObject for Post {
+///     fn from_object(object: Object) -> Result<Self, Error> {
+///         let mut extract = Extractor::from_object(object, "posts")?;
+///
+///         Ok(Post {
+///             id: extract.id()?,
+///             title: extract.attribute("title")?,
+///             author: extract.has_one("author", "users")?,
+///         })
+///     }
+/// }
+///
+/// let post: Post = doc::object_from_str(r#"{
+///     "data": {
+///         "type": "posts",
+///         "id": "1",
+///         "attributes": { "title": "Hello" },
+///         "relationships": {
+///             "author": { "data": { "type": "users", "id": "2" } }
+///         }
+///     }
+/// }"#)?;
+///
+/// assert_eq!(post.id, 1);
+/// assert_eq!(post.title, "Hello");
+/// assert_eq!(post.author, Some(2));
+///
+/// let result: Result<Post, _> = doc::object_from_str(r#"{
+///     "data": {
+///         "type": "posts",
+///         "id": "1",
+///         "attributes": { "title": "Hello" },
+///         "relationships": {
+///             "author": { "data": { "type": "comments", "id": "2" } }
+///         }
+///     }
+/// }"#);
+///
+/// assert!(result.is_err());
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Extractor`]: ./struct.Extractor.html
+/// [`Resource`]: ../trait.Resource.html
+/// [`from_doc`]: ./fn.from_doc.html
+/// [json-api-derive]: https://docs.rs/json-api-derive/0.4
+pub trait FromObject: Sized {
+    /// Creates a value from a resource object.
+    fn from_object(object: Object) -> Result<Self, Error>;
+}
+
+impl FromObject for Object {
+    fn from_object(object: Object) -> Result<Self, Error> {
+        Ok(object)
+    }
+}
+
+/// A trait indicating that a type can be created from a resource object that does not
+/// have an id yet.
+///
+/// This is the counterpart of [`FromObject`] for the primary data of a request that
+/// creates a resource.
+///
+/// [`FromObject`]: ./trait.FromObject.html
+pub trait FromNewObject: Sized {
+    /// Creates a value from a new resource object.
+    fn from_new_object(object: NewObject) -> Result<Self, Error>;
+}
+
+impl FromNewObject for NewObject {
+    fn from_new_object(object: NewObject) -> Result<Self, Error> {
+        Ok(object)
+    }
+}
+
+/// Takes the id, attributes, and relationships out of a resource object in the
+/// primary data of a document.
+///
+/// Each method returns an error with a JSON pointer to the member of the document that
+/// cannot be converted.
+#[derive(Clone, Debug)]
+pub struct Extractor {
+    attributes: Map,
+    id: Option<String>,
+    relationships: Map<Key, Relationship>,
+}
+
+impl Extractor {
+    /// Returns a new `Extractor` for `object`. An error is returned if the type of
+    /// `object` is not `kind`.
+    pub fn from_object(object: Object, kind: &str) -> Result<Self, Error> {
+        check_kind(&object.kind, kind, "/data/type")?;
+
+        Ok(Extractor {
+            attributes: object.attributes,
+            id: Some(object.id),
+            relationships: object.relationships,
+        })
+    }
+
+    /// Returns a new `Extractor` for `object`. An error is returned if the type of
+    /// `object` is not `kind`.
+    pub fn from_new_object(object: NewObject, kind: &str) -> Result<Self, Error> {
+        check_kind(&object.kind, kind, "/data/type")?;
+
+        Ok(Extractor {
+            attributes: object.attributes,
+            id: object.id,
+            relationships: object.relationships,
+        })
+    }
+
+    /// Parses the id of the object. An error is returned if the object does not have
+    /// an id.
+    pub fn id<T>(&self) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.optional_id()? {
+            Some(id) => Ok(id),
+            None => Err(Error::invalid_document("/data", r#"missing member "id""#)),
+        }
+    }
+
+    /// Parses the id of the object, if it has one.
+    pub fn optional_id<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.id {
+            Some(ref id) => parse(id, "/data/id").map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the object has an attribute or relationship named `name`.
+    ///
+    /// The methods that remove a member read a missing member as `null`. This can be
+    /// used to tell a missing member apart from a member that is set to `null`, such as
+    /// in the body of a request that updates a resource.
+    pub fn contains(&self, name: &str) -> bool {
+        match name.parse::<Key>() {
+            Ok(ref key) => {
+                self.attributes.contains_key(key) || self.relationships.contains_key(key)
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Removes the attribute `name` from the object and deserializes it. A missing
+    /// attribute is deserialized from `null`, so it can be read as an `Option<T>`.
+    pub fn attribute<T>(&mut self, name: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let key = name.parse::<Key>()?;
+
+        match self.optional_attribute(&key)? {
+            Some(value) => Ok(value),
+            None => serde_json::from_value(JsonValue::Null).map_err(|_| {
+                let detail = format!(r#"missing attribute "{}""#, key);
+                Error::invalid_document("/data/attributes", &detail)
+            }),
+        }
+    }
+
+    /// Removes the attribute `name` from the object and deserializes it. A missing
+    /// attribute is returned as `None`.
+    pub fn optional_attribute<T>(&mut self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let key = name.parse::<Key>()?;
+        let base = pointer::to_string(&["data", "attributes", &key]);
+        let value = match self.attributes.remove(&key) {
+            Some(value) => value::convert::to_json(value),
+            None => return Ok(None),
+        };
+
+        pointer::from_value(&value).map(Some).map_err(|(path, e)| {
+            let pointer = format!("{}{}", base, pointer::to_string(path));
+            Error::invalid_document(&pointer, &e.to_string())
+        })
+    }
+
+    /// Removes the to-one relationship `name` from the object and parses the id of the
+    /// related resource. An error is returned if the related resource is not of the
+    /// type `kind`. A missing relationship is returned as `None`.
+    pub fn has_one<T>(&mut self, name: &str, kind: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.optional_has_one(name, kind).map(|value| value.unwrap_or(None))
+    }
+
+    /// Removes the to-one relationship `name` from the object and parses the id of the
+    /// related resource. A missing relationship is returned as `None` and a
+    /// relationship with `null` data as `Some(None)`.
+    pub fn optional_has_one<T>(
+        &mut self,
+        name: &str,
+        kind: &str,
+    ) -> Result<Option<Option<T>>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let key = name.parse::<Key>()?;
+        let base = pointer::to_string(&["data", "relationships", &key, "data"]);
+
+        match self.relationships.remove(&key).map(|rel| rel.data) {
+            Some(Data::Member(data)) => match *data {
+                Some(ident) => identifier(ident, kind, &base).map(|id| Some(Some(id))),
+                None => Ok(Some(None)),
+            },
+            Some(Data::Collection(_)) => {
+                let detail = "expected a resource identifier or null";
+                Err(Error::invalid_document(&base, detail))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Removes the to-many relationship `name` from the object and parses the id of
+    /// each related resource. An error is returned if a related resource is not of the
+    /// type `kind`. A missing relationship is returned as an empty vector.
+    pub fn has_many<T>(&mut self, name: &str, kind: &str) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.optional_has_many(name, kind).map(|value| value.unwrap_or_else(Vec::new))
+    }
+
+    /// Removes the to-many relationship `name` from the object and parses the id of
+    /// each related resource. A missing relationship is returned as `None`.
+    pub fn optional_has_many<T>(
+        &mut self,
+        name: &str,
+        kind: &str,
+    ) -> Result<Option<Vec<T>>, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let key = name.parse::<Key>()?;
+        let base = pointer::to_string(&["data", "relationships", &key, "data"]);
+
+        match self.relationships.remove(&key).map(|rel| rel.data) {
+            Some(Data::Collection(data)) => data.into_iter()
+                .enumerate()
+                .map(|(idx, ident)| {
+                    identifier(ident, kind, &format!("{}/{}", base, idx))
+                })
+                .collect::<Result<_, _>>()
+                .map(Some),
+            Some(Data::Member(_)) => {
+                let detail = "expected an array of resource identifiers";
+                Err(Error::invalid_document(&base, detail))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Deserialize a document with a single resource object as primary data from an IO
+/// stream of JSON text and then convert the object to a type `U`.
+pub fn object_from_reader<R, U>(data: R) -> Result<U, Error>
+where
+    R: Read,
+    U: FromObject,
+{
+    primary(&convert::read(data)?).and_then(U::from_object)
+}
+
+/// Deserialize a document with a single resource object as primary data from a string
+/// of JSON text and then convert the object to a type `U`.
+pub fn object_from_str<U: FromObject>(data: &str) -> Result<U, Error> {
+    primary(data.as_bytes()).and_then(U::from_object)
+}
+
+/// Deserialize a document with a single new resource object as primary data from an
+/// IO stream of JSON text and then convert the object to a type `U`.
+pub fn new_object_from_reader<R, U>(data: R) -> Result<U, Error>
+where
+    R: Read,
+    U: FromNewObject,
+{
+    primary(&convert::read(data)?).and_then(U::from_new_object)
+}
+
+/// Deserialize a document with a single new resource object as primary data from a
+/// string of JSON text and then convert the object to a type `U`.
+pub fn new_object_from_str<U: FromNewObject>(data: &str) -> Result<U, Error> {
+    primary(data.as_bytes()).and_then(U::from_new_object)
+}
+
+fn primary<T: PrimaryData>(data: &[u8]) -> Result<T, Error> {
+    match convert::parse::<T>(data)? {
+        Document::Ok {
+            data: Data::Member(data),
+            ..
+        } => (*data).ok_or_else(|| {
+            Error::invalid_document("/data", "expected a resource object")
+        }),
+        Document::Ok { .. } => Err(Error::invalid_document(
+            "/data",
+            "expected a single resource object",
+        )),
+        Document::Err { .. } => Err(Error::from("Document contains one or more error(s)")),
+    }
+}
+
+fn identifier<T>(ident: Identifier, kind: &str, base: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    check_kind(&ident.kind, kind, &format!("{}/type", base))?;
+    parse(&ident.id, &format!("{}/id", base))
+}
+
+fn check_kind(kind: &Key, expected: &str, pointer: &str) -> Result<(), Error> {
+    if &**kind == expected {
+        return Ok(());
+    }
+
+    let detail = format!(
+        r#"expected a resource of type "{}", found "{}""#,
+        expected, kind
+    );
+
+    Err(Error::invalid_document(pointer, &detail))
+}
+
+fn parse<T>(value: &str, pointer: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e: T::Err| Error::invalid_document(pointer, &e.to_string()))
+}
@@ -0,0 +1,166 @@
+use doc::{Data, Document, Identifier, Object};
+use value::Map;
+
+/// An index of the resource objects in a compound document that can be used to resolve
+/// relationships.
+///
+/// The index is built once when the graph is created. Each lookup after that does not
+/// require a scan of the document's primary data or included resources.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{Document, Graph, Identifier, Object};
+///
+/// let doc: Document<Object> = serde_json::from_str(r#"{
+///     "data": {
+///         "type": "articles",
+///         "id": "1",
+///         "relationships": {
+///             "author": { "data": { "type": "people", "id": "9" } },
+///             "comments": { "data": [{ "type": "comments", "id": "5" }] }
+///         }
+///     },
+///     "included": [{
+///         "type": "people",
+///         "id": "9",
+///         "attributes": { "name": "Dan Gebhardt" }
+///     }, {
+///         "type": "comments",
+///         "id": "5",
+///         "attributes": { "body": "First!" }
+///     }]
+/// }"#)?;
+///
+/// let graph = Graph::new(&doc);
+/// let article = Identifier::new("articles".parse()?, "1".to_owned());
+///
+/// let author = graph.related(&article, "author").unwrap();
+/// assert_eq!(author.attributes.get("name"), Some(&"Dan Gebhardt".into()));
+///
+/// let comments = graph.related_many(&article, "comments");
+/// assert_eq!(comments.len(), 1);
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Graph<'a> {
+    index: Map<(&'a str, &'a str), &'a Object>,
+}
+
+impl<'a> Graph<'a> {
+    /// Returns a new `Graph` containing the primary data and included resources of
+    /// `doc`. If `doc` contains errors, the graph will be empty.
+    pub fn new(doc: &'a Document<Object>) -> Self {
+        let mut index = Map::new();
+
+        if let Document::Ok {
+            ref data,
+            ref included,
+            ..
+        } = *doc
+        {
+            let data = match *data {
+                Data::Member(ref data) => data.iter().collect(),
+                Data::Collection(ref data) => data.iter().collect::<Vec<_>>(),
+            };
+
+            for object in data.into_iter().chain(included) {
+                index.insert((&*object.kind, &*object.id), object);
+            }
+        }
+
+        Graph { index }
+    }
+
+    /// Returns the resource object identified by `ident`, if it is present in the
+    /// document.
+    pub fn get(&self, ident: &Identifier) -> Option<&'a Object> {
+        self.index.get(&(&*ident.kind, &*ident.id)).cloned()
+    }
+
+    /// Returns the number of resource objects in the graph.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if the graph does not contain any resource objects.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the resource object that is referenced by the to-one relationship `name`
+    /// of the resource identified by `ident`.
+    ///
+    /// If either resource is not present in the document, or the relationship does not
+    /// exist or is a to-many relationship, `None` is returned.
+    pub fn related(&self, ident: &Identifier, name: &str) -> Option<&'a Object> {
+        let rel = self.get(ident)?.relationships.get(name)?;
+
+        match rel.data {
+            Data::Member(ref data) => data.as_ref().as_ref().and_then(|item| self.get(item)),
+            Data::Collection(_) => None,
+        }
+    }
+
+    /// Returns the resource objects that are referenced by the relationship `name` of
+    /// the resource identified by `ident`.
+    ///
+    /// Resources that are referenced by the relationship but are not present in the
+    /// document are skipped.
+    pub fn related_many(&self, ident: &Identifier, name: &str) -> Vec<&'a Object> {
+        let rel = match self.get(ident).and_then(|item| item.relationships.get(name)) {
+            Some(rel) => rel,
+            None => return Vec::new(),
+        };
+
+        match rel.data {
+            Data::Member(ref data) => data.iter().filter_map(|item| self.get(item)).collect(),
+            Data::Collection(ref data) => data.iter().filter_map(|item| self.get(item)).collect(),
+        }
+    }
+}
+
+impl Document<Object> {
+    /// Returns a [`Graph`] that can be used to resolve relationships in the document.
+    ///
+    /// [`Graph`]: ./struct.Graph.html
+    pub fn graph(&self) -> Graph<'_> {
+        Graph::new(self)
+    }
+
+    /// Returns the resource object that is referenced by the to-one relationship `name`
+    /// of the resource identified by `ident`.
+    ///
+    /// This method builds a new [`Graph`] each time it is called. If you need to
+    /// resolve more than one relationship, use the [`graph`] method instead.
+    ///
+    /// [`Graph`]: ./struct.Graph.html
+    /// [`graph`]: #method.graph
+    pub fn related(&self, ident: &Identifier, name: &str) -> Option<&Object> {
+        self.graph().related(ident, name)
+    }
+
+    /// Returns the resource objects that are referenced by the relationship `name` of
+    /// the resource identified by `ident`.
+    ///
+    /// This method builds a new [`Graph`] each time it is called. If you need to
+    /// resolve more than one relationship, use the [`graph`] method instead.
+    ///
+    /// [`Graph`]: ./struct.Graph.html
+    /// [`graph`]: #method.graph
+    pub fn related_many(&self, ident: &Identifier, name: &str) -> Vec<&Object> {
+        self.graph().related_many(ident, name)
+    }
+}
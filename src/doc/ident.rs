@@ -2,30 +2,43 @@ use std::cmp::{Eq, PartialEq};
 use std::hash::{Hash, Hasher};
 use std::mem;
 
-use doc::{Data, Document, Object, PrimaryData};
+use serde::de::{Deserialize, Deserializer};
+
+use doc::{validate, Data, Document, Flattener, Object, PrimaryData, Version};
 use error::Error;
 use query::Query;
 use sealed::Sealed;
-use value::{Key, Map, Set, Value};
+use value::{Key, Map, Value};
 use view::Render;
 
 /// Identifies an individual resource. Commonly found in an object's relationships.
 ///
-/// Identifiers share their [equality] and [hashing] behavior with [`Object`]. For more
-/// information, check out the *[resource identifier objects]* section of the
-/// JSON API specification.
+/// An identifier must have an `id`, a `lid`, or both. A `lid` is only accepted in a
+/// document that declares version 1.1 of the specification.
+///
+/// Identifiers share their [equality] and [hashing] behavior with [`Object`]. An
+/// identifier that references a resource by [`lid`] alone is only equal to identifiers
+/// with the same [`lid`]. For more information, check out the *[resource identifier
+/// objects]* section of the JSON API specification.
+///
+/// [`lid`]: #structfield.lid
 ///
 /// [`Object`]: ./struct.Object.html
 /// [equality]: ./struct.Object.html#equality
 /// [hashing]: ./struct.Object.html#hashing
 /// [resource identifier objects]: https://goo.gl/vgfzru
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Identifier {
     /// A string that contains a unique identfier for this resource type (`kind`). For
     /// more information, check out the *[identification]* section of the JSON API
     /// specification.
     ///
+    /// This field is empty if the identifier references a resource by [`lid`] alone. In
+    /// that case, it will not be serialized.
+    ///
+    /// [`lid`]: #structfield.lid
     /// [identification]: https://goo.gl/3s681i
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub id: String,
 
     /// Describes resources that share common attributes and relationships. This field is
@@ -37,6 +50,14 @@ pub struct Identifier {
     #[serde(rename = "type")]
     pub kind: Key,
 
+    /// A string that uniquely identifies a resource that was created in the same
+    /// request document and does not yet have a server-assigned [`id`]. Introduced in
+    /// version 1.1 of the specification.
+    ///
+    /// [`id`]: #structfield.id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lid: Option<String>,
+
     /// Non-standard meta information. If this value of this field is empty, it will not
     /// be serialized. For more information, check out the *[meta information]* section
     /// of the JSON API specification.
@@ -74,10 +95,77 @@ impl Identifier {
         Identifier {
             id,
             kind,
+            lid: Default::default(),
             meta: Default::default(),
             _ext: (),
         }
     }
+
+    /// Returns a new `Identifier` that references a resource by `lid` rather than `id`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Identifier;
+    ///
+    /// let ident = Identifier::local("users".parse()?, "new-user".to_owned());
+    ///
+    /// assert!(ident.id.is_empty());
+    /// assert_eq!(ident.lid, Some("new-user".to_owned()));
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn local(kind: Key, lid: String) -> Self {
+        let mut ident = Identifier::new(kind, String::new());
+
+        ident.lid = Some(lid);
+        ident
+    }
+}
+
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        struct IdentifierRepr {
+            id: Option<String>,
+            lid: Option<String>,
+            #[serde(default)]
+            meta: Map,
+            #[serde(rename = "type")]
+            kind: Key,
+        }
+
+        let IdentifierRepr { id, lid, meta, kind } =
+            IdentifierRepr::deserialize(deserializer)?;
+
+        let mut ident = match (id, lid) {
+            (Some(id), lid) => {
+                let mut ident = Identifier::new(kind, id);
+
+                ident.lid = lid;
+                ident
+            }
+            (None, Some(lid)) => Identifier::local(kind, lid),
+            (None, None) => return Err(D::Error::missing_field("id")),
+        };
+
+        ident.meta = meta;
+        Ok(ident)
+    }
 }
 
 impl Eq for Identifier {}
@@ -107,7 +195,7 @@ impl Hash for Identifier {
 
 impl PartialEq for Identifier {
     fn eq(&self, rhs: &Identifier) -> bool {
-        self.id == rhs.id && self.kind == rhs.kind
+        self.id == rhs.id && self.kind == rhs.kind && (!self.id.is_empty() || self.lid == rhs.lid)
     }
 }
 
@@ -144,11 +232,24 @@ impl Render<Identifier> for Vec<Identifier> {
 }
 
 impl PrimaryData for Identifier {
-    fn flatten(self, incl: &Set<Object>) -> Value {
-        incl.into_iter()
-            .find(|item| self == **item)
-            .map(|item| item.clone().flatten(incl))
-            .unwrap_or_else(|| self.id.clone().into())
+    fn kind(&self) -> &Key {
+        &self.kind
+    }
+
+    fn required_version(&self) -> Version {
+        if self.lid.is_some() {
+            Version::V1_1
+        } else {
+            Version::V1
+        }
+    }
+
+    fn validate(&self, _: &str, state: &mut validate::State) {
+        state.ident(self);
+    }
+
+    fn flatten(self, state: &mut Flattener) -> Value {
+        state.embed(self)
     }
 }
 
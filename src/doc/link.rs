@@ -8,6 +8,7 @@ use http::Uri;
 use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
+use doc::Version;
 use error::Error;
 use value::Map;
 
@@ -40,6 +41,16 @@ pub struct Link {
     /// The link’s URI.
     pub href: Uri,
 
+    /// The natural language(s) of the link’s target. If this value of this field
+    /// contains a single language tag, it will be serialized as a string. Introduced in
+    /// version 1.1 of the specification.
+    pub hreflang: Vec<String>,
+
+    /// The media type of the link’s target. This field is derived from the `type`
+    /// field if the link is deserialized. Introduced in version 1.1 of the
+    /// specification.
+    pub media_type: Option<String>,
+
     /// Non-standard meta information. If this value of this field is empty, the link
     /// will be serialized as a string containing the contents of `href`. For more
     /// information, check out the *[meta information]* section of the JSON API
@@ -48,10 +59,58 @@ pub struct Link {
     /// [meta information]: https://goo.gl/LyrGF8
     pub meta: Map,
 
+    /// The link’s relation type. Introduced in version 1.1 of the specification.
+    pub rel: Option<String>,
+
+    /// A human-readable label for the link’s target. Introduced in version 1.1 of the
+    /// specification.
+    pub title: Option<String>,
+
     /// Private field for backwards compatibility.
     _ext: (),
 }
 
+impl Link {
+    /// Returns the earliest version of the specification that supports each of the
+    /// members that are present in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Link, Version};
+    ///
+    /// let mut link = "https://rust-lang.org".parse::<Link>()?;
+    /// assert_eq!(link.required_version(), Version::V1);
+    ///
+    /// link.title = Some("Rust".to_owned());
+    /// assert_eq!(link.required_version(), Version::V1_1);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn required_version(&self) -> Version {
+        let is_v1 = self.hreflang.is_empty()
+            && self.media_type.is_none()
+            && self.rel.is_none()
+            && self.title.is_none();
+
+        if is_v1 {
+            Version::V1
+        } else {
+            Version::V1_1
+        }
+    }
+}
+
 impl Deref for Link {
     type Target = Uri;
 
@@ -74,7 +133,11 @@ impl FromStr for Link {
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         Ok(Link {
             href: value.parse()?,
+            hreflang: Default::default(),
+            media_type: Default::default(),
             meta: Default::default(),
+            rel: Default::default(),
+            title: Default::default(),
             _ext: (),
         })
     }
@@ -119,7 +182,18 @@ impl<'de> Deserialize<'de> for Link {
         #[serde(field_identifier, rename_all = "lowercase")]
         enum Field {
             Href,
+            Hreflang,
             Meta,
+            Rel,
+            Title,
+            Type,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Hreflang {
+            One(String),
+            Many(Vec<String>),
         }
 
         struct LinkVisitor;
@@ -143,29 +217,64 @@ impl<'de> Deserialize<'de> for Link {
                 V: MapAccess<'de>,
             {
                 let mut href = None;
+                let mut hreflang = None;
+                let mut media_type = None;
                 let mut meta = None;
+                let mut rel = None;
+                let mut title = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::Href if href.is_some() => {
                             return Err(de::Error::duplicate_field("href"))
                         }
+                        Field::Hreflang if hreflang.is_some() => {
+                            return Err(de::Error::duplicate_field("hreflang"))
+                        }
                         Field::Meta if meta.is_some() => {
                             return Err(de::Error::duplicate_field("meta"))
                         }
+                        Field::Rel if rel.is_some() => {
+                            return Err(de::Error::duplicate_field("rel"))
+                        }
+                        Field::Title if title.is_some() => {
+                            return Err(de::Error::duplicate_field("title"))
+                        }
+                        Field::Type if media_type.is_some() => {
+                            return Err(de::Error::duplicate_field("type"))
+                        }
                         Field::Href => {
                             let next = map.next_value::<String>()?;
                             href = Some(next.parse().map_err(de::Error::custom)?);
                         }
+                        Field::Hreflang => {
+                            hreflang = Some(match map.next_value()? {
+                                Hreflang::One(value) => vec![value],
+                                Hreflang::Many(value) => value,
+                            });
+                        }
                         Field::Meta => {
                             meta = Some(map.next_value()?);
                         }
+                        Field::Rel => {
+                            rel = Some(map.next_value()?);
+                        }
+                        Field::Title => {
+                            title = Some(map.next_value()?);
+                        }
+                        Field::Type => {
+                            media_type = Some(map.next_value()?);
+                        }
                     }
                 }
 
                 Ok(Link {
                     href: href.ok_or_else(|| de::Error::missing_field("href"))?,
+                    hreflang: hreflang.unwrap_or_default(),
                     meta: meta.unwrap_or_default(),
+                    media_type,
+                    rel,
+                    title,
                     _ext: (),
                 })
             }
@@ -183,15 +292,36 @@ impl Serialize for Link {
         let href = self.href.to_string();
         let meta = &self.meta;
 
-        if meta.is_empty() {
-            serializer.serialize_str(&href)
-        } else {
-            let mut state = serializer.serialize_struct("Link", 2)?;
+        if meta.is_empty() && self.required_version() == Version::V1 {
+            return serializer.serialize_str(&href);
+        }
+
+        let mut state = serializer.serialize_struct("Link", 6)?;
 
-            state.serialize_field("href", &href)?;
+        state.serialize_field("href", &href)?;
+
+        match self.hreflang.len() {
+            0 => (),
+            1 => state.serialize_field("hreflang", &self.hreflang[0])?,
+            _ => state.serialize_field("hreflang", &self.hreflang)?,
+        }
+
+        if !meta.is_empty() {
             state.serialize_field("meta", meta)?;
+        }
 
-            state.end()
+        if let Some(ref rel) = self.rel {
+            state.serialize_field("rel", rel)?;
         }
+
+        if let Some(ref title) = self.title {
+            state.serialize_field("title", title)?;
+        }
+
+        if let Some(ref media_type) = self.media_type {
+            state.serialize_field("type", media_type)?;
+        }
+
+        state.end()
     }
 }
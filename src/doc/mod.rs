@@ -1,9 +1,17 @@
 //! Components of a JSON API document.
 
+pub mod atomic;
+pub mod borrowed;
+pub mod validate;
+
+mod builder;
 mod convert;
+mod extract;
+mod graph;
 mod ident;
 mod link;
 mod object;
+mod pointer;
 mod relationship;
 mod specification;
 
@@ -11,8 +19,8 @@ mod error;
 
 use std::iter::FromIterator;
 
-use serde::de::DeserializeOwned;
-use serde::ser::Serialize;
+use serde::de::{Deserialize, DeserializeOwned, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 use error::Error;
 use query::Query;
@@ -20,8 +28,12 @@ use sealed::Sealed;
 use value::{Key, Map, Set, Value};
 use view::Render;
 
+pub use self::builder::DocumentBuilder;
 pub use self::convert::*;
 pub use self::error::{ErrorObject, ErrorSource};
+pub use self::extract::{new_object_from_reader, new_object_from_str, object_from_reader,
+                        object_from_str, Extractor, FromNewObject, FromObject};
+pub use self::graph::Graph;
 pub use self::ident::Identifier;
 pub use self::link::Link;
 pub use self::object::{NewObject, Object};
@@ -32,7 +44,16 @@ pub use self::specification::{JsonApi, Version};
 /// document.
 pub trait PrimaryData: DeserializeOwned + Sealed + Serialize {
     #[doc(hidden)]
-    fn flatten(self, &Set<Object>) -> Value;
+    fn flatten(self, &mut Flattener) -> Value;
+
+    #[doc(hidden)]
+    fn kind(&self) -> &Key;
+
+    #[doc(hidden)]
+    fn required_version(&self) -> Version;
+
+    #[doc(hidden)]
+    fn validate(&self, pointer: &str, state: &mut validate::State);
 }
 
 /// Represents a compound JSON API document.
@@ -40,9 +61,13 @@ pub trait PrimaryData: DeserializeOwned + Sealed + Serialize {
 /// For more information, check out the *[document structure]* section of the JSON API
 /// specification.
 ///
+/// A document can only contain members that were introduced in a later version of the
+/// specification (e.g. `lid`) if the version is declared in the document's `jsonapi`
+/// object. Serializing or deserializing a document that does not meet this requirement
+/// will fail.
+///
 /// [document structure]: https://goo.gl/CXTNmt
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
-#[serde(bound = "T: PrimaryData", untagged)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Document<T: PrimaryData> {
     /// Does not contain errors.
     Ok {
@@ -54,7 +79,6 @@ pub enum Document<T: PrimaryData> {
 
         /// Included resources, resolved from the `include` query parameter of a client
         /// request.
-        #[serde(default, skip_serializing_if = "Set::is_empty")]
         included: Set<Object>,
 
         /// Information about this implementation of the specification that the
@@ -62,7 +86,6 @@ pub enum Document<T: PrimaryData> {
         /// object]* section of the JSON API specification.
         ///
         /// [JSON API object]: https://goo.gl/hZUcEt
-        #[serde(default)]
         jsonapi: JsonApi,
 
         /// Contains relevant links. If this value of this field is empty, it will not be
@@ -70,7 +93,6 @@ pub enum Document<T: PrimaryData> {
         /// API specification.
         ///
         /// [links]: https://goo.gl/E4E6Vt
-        #[serde(default, skip_serializing_if = "Map::is_empty")]
         links: Map<Key, Link>,
 
         /// Non-standard meta information. If this value of this field is empty, it will
@@ -78,26 +100,24 @@ pub enum Document<T: PrimaryData> {
         /// information]* section of the JSON API specification.
         ///
         /// [meta information]: https://goo.gl/LyrGF8
-        #[serde(default, skip_serializing_if = "Map::is_empty")]
         meta: Map,
     },
 
     /// Contains 1 or more error(s).
     Err {
         errors: Vec<ErrorObject>,
-
-        #[serde(default)]
         jsonapi: JsonApi,
-
-        #[serde(default, skip_serializing_if = "Map::is_empty")]
         links: Map<Key, Link>,
-
-        #[serde(default, skip_serializing_if = "Map::is_empty")]
         meta: Map,
     },
 }
 
 impl<T: PrimaryData> Document<T> {
+    /// Returns a document builder that can be used to create a new document.
+    pub fn builder() -> DocumentBuilder<T> {
+        Default::default()
+    }
+
     /// Returns `true` if the document does not contain any errors.
     pub fn is_ok(&self) -> bool {
         match *self {
@@ -113,6 +133,268 @@ impl<T: PrimaryData> Document<T> {
             Document::Err { .. } => false,
         }
     }
+
+    /// Returns a reference to the document's `jsonapi` object.
+    pub fn jsonapi(&self) -> &JsonApi {
+        match *self {
+            Document::Ok { ref jsonapi, .. } | Document::Err { ref jsonapi, .. } => jsonapi,
+        }
+    }
+
+    /// Returns the earliest version of the specification that supports each of the
+    /// members that are present in the document.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Document, Identifier, Version};
+    ///
+    /// let mut ident = Identifier::new("users".parse()?, "1".to_owned());
+    /// let doc: Document<Identifier> = json_api::to_doc(ident.clone(), None)?;
+    ///
+    /// assert_eq!(doc.required_version(), Version::V1);
+    ///
+    /// ident.lid = Some("local-1".to_owned());
+    /// let doc: Document<Identifier> = json_api::to_doc(ident, None)?;
+    ///
+    /// assert_eq!(doc.required_version(), Version::V1_1);
+    /// assert!(json_api::to_string(doc, None).is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn required_version(&self) -> Version {
+        let (jsonapi, links, rest) = match *self {
+            Document::Ok {
+                ref data,
+                ref included,
+                ref jsonapi,
+                ref links,
+                ..
+            } => {
+                let data = match *data {
+                    Data::Member(ref data) => data.iter().map(T::required_version).max(),
+                    Data::Collection(ref data) => data.iter().map(T::required_version).max(),
+                };
+                let included = included.iter().map(Object::required_version).max();
+
+                (jsonapi, links, data.into_iter().chain(included).max())
+            }
+            Document::Err {
+                ref errors,
+                ref jsonapi,
+                ref links,
+                ..
+            } => {
+                let errors = errors
+                    .iter()
+                    .flat_map(|error| error.links.values())
+                    .map(Link::required_version)
+                    .max();
+
+                (jsonapi, links, errors)
+            }
+        };
+
+        links
+            .values()
+            .map(Link::required_version)
+            .chain(rest)
+            .chain(Some(jsonapi.required_version()))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Checks the document against the requirements of the specification that cannot
+    /// be expressed in the type system. Returns an error object for each violation,
+    /// with a JSON pointer to the member that caused it.
+    ///
+    /// The requirements include *[full linkage]* of included resources, the uniqueness
+    /// of each resource in the document, the reserved member names `id` and `type`, and
+    /// the declared version of the specification. For more information, check out the
+    /// [`validate`] module.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Data, Document, Object};
+    ///
+    /// let mut post = Object::new("posts".parse()?, "1".to_owned());
+    /// post.attributes.insert("type".parse()?, "article".into());
+    ///
+    /// let doc: Document<Object> = json_api::to_doc(post, None)?;
+    /// let errors = doc.validate();
+    ///
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(
+    ///     errors[0].source.as_ref().and_then(|s| s.pointer.as_ref()),
+    ///     Some(&"/data/attributes/type".to_owned())
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`validate`]: ./validate/index.html
+    /// [full linkage]: http://jsonapi.org/format/#document-compound-documents
+    pub fn validate(&self) -> Vec<ErrorObject> {
+        validate::document(self)
+    }
+
+    fn check_version(&self) -> Result<(), String> {
+        let declared = self.jsonapi().version;
+        let required = self.required_version();
+
+        if required > declared {
+            Err(format!(
+                "document contains members from version {} of the specification \
+                 but declares version {}",
+                required, declared
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'de, T: PrimaryData> Deserialize<'de> for Document<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        #[serde(bound = "T: PrimaryData", untagged)]
+        enum DocumentRepr<T: PrimaryData> {
+            Ok {
+                data: Data<T>,
+                #[serde(default)]
+                included: Set<Object>,
+                #[serde(default)]
+                jsonapi: JsonApi,
+                #[serde(default)]
+                links: Map<Key, Link>,
+                #[serde(default)]
+                meta: Map,
+            },
+            Err {
+                errors: Vec<ErrorObject>,
+                #[serde(default)]
+                jsonapi: JsonApi,
+                #[serde(default)]
+                links: Map<Key, Link>,
+                #[serde(default)]
+                meta: Map,
+            },
+        }
+
+        let doc = match DocumentRepr::deserialize(deserializer)? {
+            DocumentRepr::Ok {
+                data,
+                included,
+                jsonapi,
+                links,
+                meta,
+            } => Document::Ok {
+                data,
+                included,
+                jsonapi,
+                links,
+                meta,
+            },
+            DocumentRepr::Err {
+                errors,
+                jsonapi,
+                links,
+                meta,
+            } => Document::Err {
+                errors,
+                jsonapi,
+                links,
+                meta,
+            },
+        };
+
+        doc.check_version().map_err(D::Error::custom)?;
+        Ok(doc)
+    }
+}
+
+impl<T: PrimaryData> Serialize for Document<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error;
+
+        self.check_version().map_err(S::Error::custom)?;
+
+        let mut state = serializer.serialize_struct("Document", 5)?;
+
+        match *self {
+            Document::Ok {
+                ref data,
+                ref included,
+                ref jsonapi,
+                ref links,
+                ref meta,
+            } => {
+                state.serialize_field("data", data)?;
+
+                if !included.is_empty() {
+                    state.serialize_field("included", included)?;
+                }
+
+                state.serialize_field("jsonapi", jsonapi)?;
+
+                if !links.is_empty() {
+                    state.serialize_field("links", links)?;
+                }
+
+                if !meta.is_empty() {
+                    state.serialize_field("meta", meta)?;
+                }
+            }
+            Document::Err {
+                ref errors,
+                ref jsonapi,
+                ref links,
+                ref meta,
+            } => {
+                state.serialize_field("errors", errors)?;
+                state.serialize_field("jsonapi", jsonapi)?;
+
+                if !links.is_empty() {
+                    state.serialize_field("links", links)?;
+                }
+
+                if !meta.is_empty() {
+                    state.serialize_field("meta", meta)?;
+                }
+            }
+        }
+
+        state.end()
+    }
 }
 
 impl<T: PrimaryData> Render<T> for Document<T> {
@@ -2,11 +2,12 @@ use std::cmp::{Eq, PartialEq};
 use std::hash::{Hash, Hasher};
 use std::mem;
 
-use doc::{Data, Document, Identifier, Link, PrimaryData, Relationship};
+use doc::{validate, Data, Document, Flattener, Identifier, Link, PrimaryData, Relationship,
+          Version};
 use error::Error;
 use query::Query;
 use sealed::Sealed;
-use value::{Key, Map, Set, Value};
+use value::{Key, Map, Value};
 use view::Render;
 
 /// A preexisting resource. Commonly found in the document of a response or `PATCH`
@@ -297,25 +298,41 @@ impl Render<Object> for Vec<Object> {
 }
 
 impl PrimaryData for Object {
-    fn flatten(self, incl: &Set<Object>) -> Value {
+    fn kind(&self) -> &Key {
+        &self.kind
+    }
+
+    fn flatten(self, state: &mut Flattener) -> Value {
         #[cfg_attr(rustfmt, rustfmt_skip)]
-        let Object { id, attributes, relationships, .. } = self;
+        let Object { id, kind, attributes, relationships, .. } = self;
         let mut map = {
             let size = attributes.len() + relationships.len() + 1;
             Map::with_capacity(size)
         };
 
-        map.insert(Key::from_raw("id".to_owned()), Value::String(id));
+        state.enter(&kind, &id);
+
+        let key = Key::from_raw("id".to_owned());
+
+        state.member(&key, "");
+        map.insert(key, Value::String(id));
+
+        for key in attributes.keys() {
+            state.member(key, "attributes");
+        }
+
         map.extend(attributes);
 
         for (key, value) in relationships {
+            state.member(&key, "relationships");
+
             let value = match value.data {
                 Data::Member(data) => match *data {
-                    Some(item) => item.flatten(incl),
+                    Some(item) => item.flatten(state),
                     None => Value::Null,
                 },
                 Data::Collection(data) => {
-                    let iter = data.into_iter().map(|item| item.flatten(incl));
+                    let iter = data.into_iter().map(|item| item.flatten(state));
                     Value::Array(iter.collect())
                 }
             };
@@ -323,8 +340,26 @@ impl PrimaryData for Object {
             map.insert(key, value);
         }
 
+        state.leave();
         Value::Object(map)
     }
+
+    fn required_version(&self) -> Version {
+        let links = self.links.values().map(Link::required_version);
+        let related = self.relationships.values().map(Relationship::required_version);
+
+        links.chain(related).max().unwrap_or_default()
+    }
+
+    fn validate(&self, pointer: &str, state: &mut validate::State) {
+        state.resource(
+            pointer,
+            &self.kind,
+            Some(&self.id),
+            &self.attributes,
+            &self.relationships,
+        );
+    }
 }
 
 impl Sealed for Object {}
@@ -364,6 +399,15 @@ pub struct NewObject {
     #[serde(rename = "type")]
     pub kind: Key,
 
+    /// An optional string that uniquely identifies this resource within the request
+    /// document. Other resources in the same document can reference this resource by
+    /// `lid` before it has been assigned an [`id`]. Introduced in version 1.1 of the
+    /// specification.
+    ///
+    /// [`id`]: #structfield.id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lid: Option<String>,
+
     /// Contains relevant links. If this value of this field is empty, it will not be
     /// serialized. For more information, check out the *[links]* section of the JSON
     /// API specification.
@@ -418,6 +462,7 @@ impl NewObject {
             kind,
             id: Default::default(),
             attributes: Default::default(),
+            lid: Default::default(),
             links: Default::default(),
             meta: Default::default(),
             relationships: Default::default(),
@@ -427,7 +472,11 @@ impl NewObject {
 }
 
 impl PrimaryData for NewObject {
-    fn flatten(self, _: &Set<Object>) -> Value {
+    fn kind(&self) -> &Key {
+        &self.kind
+    }
+
+    fn flatten(self, state: &mut Flattener) -> Value {
         #[cfg_attr(rustfmt, rustfmt_skip)]
         let NewObject { id, attributes, relationships, .. } = self;
         let mut map = {
@@ -436,12 +485,21 @@ impl PrimaryData for NewObject {
         };
 
         if let Some(value) = id {
-            map.insert(Key::from_raw("id".to_owned()), Value::String(value));
+            let key = Key::from_raw("id".to_owned());
+
+            state.member(&key, "");
+            map.insert(key, Value::String(value));
+        }
+
+        for key in attributes.keys() {
+            state.member(key, "attributes");
         }
 
         map.extend(attributes);
 
         for (key, value) in relationships {
+            state.member(&key, "relationships");
+
             let value = match value.data {
                 Data::Member(data) => match *data {
                     Some(Identifier { id, .. }) => Value::String(id),
@@ -455,6 +513,24 @@ impl PrimaryData for NewObject {
 
         Value::Object(map)
     }
+
+    fn required_version(&self) -> Version {
+        let links = self.links.values().map(Link::required_version);
+        let related = self.relationships.values().map(Relationship::required_version);
+        let lid = self.lid.as_ref().map(|_| Version::V1_1);
+
+        links.chain(related).chain(lid).max().unwrap_or_default()
+    }
+
+    fn validate(&self, pointer: &str, state: &mut validate::State) {
+        state.resource(
+            pointer,
+            &self.kind,
+            self.id.as_ref().map(String::as_str),
+            &self.attributes,
+            &self.relationships,
+        );
+    }
 }
 
 impl Render<NewObject> for NewObject {
@@ -0,0 +1,415 @@
+//! Keeps track of the location of the value that is being deserialized so a failure
+//! can be reported with a JSON pointer.
+
+use std::cell::RefCell;
+use std::fmt::{self, Formatter};
+
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, EnumAccess, MapAccess,
+                SeqAccess, VariantAccess, Visitor};
+use serde_json::{self, Value as JsonValue};
+
+/// Deserialize a `T` from `value`. If deserialization fails, the error is returned
+/// with the path segments of the value that caused it.
+pub(crate) fn from_value<'de, T>(
+    value: &'de JsonValue,
+) -> Result<T, (Vec<String>, serde_json::Error)>
+where
+    T: Deserialize<'de>,
+{
+    let track = Track::default();
+    let result = T::deserialize(Wrap::new(value, &track));
+
+    result.map_err(|e| (track.failed.into_inner().unwrap_or_default(), e))
+}
+
+/// Returns a JSON pointer from a list of path segments.
+pub(crate) fn to_string<I>(segments: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    segments.into_iter().fold(String::new(), |mut pointer, segment| {
+        pointer.push('/');
+        pointer.push_str(&segment.as_ref().replace('~', "~0").replace('/', "~1"));
+        pointer
+    })
+}
+
+#[derive(Default)]
+struct Track {
+    failed: RefCell<Option<Vec<String>>>,
+    key: RefCell<Option<String>>,
+    path: RefCell<Vec<String>>,
+}
+
+impl Track {
+    fn fail<E>(&self, e: E) -> E {
+        let mut failed = self.failed.borrow_mut();
+
+        // The path of an error in a map key is recorded once the key is known.
+        if failed.is_none() && self.key.borrow().is_none() {
+            *failed = Some(self.path.borrow().clone());
+        }
+
+        e
+    }
+
+    fn scope<T, E, F>(&self, segment: String, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        self.path.borrow_mut().push(segment);
+
+        let result = f().map_err(|e| self.fail(e));
+
+        self.path.borrow_mut().pop();
+        result
+    }
+
+    fn capture(&self, value: &str) {
+        if let Some(ref mut key) = *self.key.borrow_mut() {
+            key.push_str(value);
+        }
+    }
+}
+
+struct Wrap<'a, T> {
+    inner: T,
+    track: &'a Track,
+}
+
+impl<'a, T> Wrap<'a, T> {
+    fn new(inner: T, track: &'a Track) -> Self {
+        Wrap { inner, track }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error>
+            where
+                V: Visitor<'de>,
+            {
+                let track = self.track;
+                let visitor = Wrap::new(visitor, track);
+
+                self.inner
+                    .$method($($arg,)* visitor)
+                    .map_err(|e| track.fail(e))
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, D> Deserializer<'de> for Wrap<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+impl<'a, 'de, S> DeserializeSeed<'de> for Wrap<'a, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.inner.deserialize(Wrap::new(deserializer, self.track))
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty);)*) => {
+        $(
+            fn $method<E: de::Error>(self, value: $ty) -> Result<Self::Value, E> {
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, V> Visitor<'de> for Wrap<'a, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        self.inner.expecting(f)
+    }
+
+    forward_visit! {
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_bytes(&[u8]);
+        visit_borrowed_bytes(&'de [u8]);
+        visit_byte_buf(Vec<u8>);
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        self.track.capture(value);
+        self.inner.visit_str(value)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, value: &'de str) -> Result<Self::Value, E> {
+        self.track.capture(value);
+        self.inner.visit_borrowed_str(value)
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        self.track.capture(&value);
+        self.inner.visit_string(value)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.inner.visit_some(Wrap::new(deserializer, self.track))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.inner
+            .visit_newtype_struct(Wrap::new(deserializer, self.track))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.inner.visit_seq(Seq {
+            inner: seq,
+            index: 0,
+            track: self.track,
+        })
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.inner.visit_map(Map {
+            inner: map,
+            key: None,
+            track: self.track,
+        })
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        self.inner.visit_enum(Wrap::new(data, self.track))
+    }
+}
+
+struct Seq<'a, A> {
+    inner: A,
+    index: usize,
+    track: &'a Track,
+}
+
+impl<'a, 'de, A> SeqAccess<'de> for Seq<'a, A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let track = self.track;
+        let inner = &mut self.inner;
+        let result = track.scope(self.index.to_string(), || {
+            inner.next_element_seed(Wrap::new(seed, track))
+        });
+
+        self.index += 1;
+        result
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct Map<'a, A> {
+    inner: A,
+    key: Option<String>,
+    track: &'a Track,
+}
+
+impl<'a, 'de, A> MapAccess<'de> for Map<'a, A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let track = self.track;
+
+        *track.key.borrow_mut() = Some(String::new());
+
+        let result = self.inner.next_key_seed(Wrap::new(seed, track));
+        let key = track.key.borrow_mut().take();
+
+        match result {
+            Ok(value) => {
+                self.key = key;
+                Ok(value)
+            }
+            Err(e) => track.scope(key.unwrap_or_default(), || Err(e)),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let track = self.track;
+        let inner = &mut self.inner;
+
+        track.scope(self.key.take().unwrap_or_default(), || {
+            inner.next_value_seed(Wrap::new(seed, track))
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'de, A> EnumAccess<'de> for Wrap<'a, A>
+where
+    A: EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = Wrap<'a, A::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let track = self.track;
+        let (value, variant) = self.inner
+            .variant_seed(Wrap::new(seed, track))
+            .map_err(|e| track.fail(e))?;
+
+        Ok((value, Wrap::new(variant, track)))
+    }
+}
+
+impl<'a, 'de, A> VariantAccess<'de> for Wrap<'a, A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        let track = self.track;
+        self.inner.unit_variant().map_err(|e| track.fail(e))
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let track = self.track;
+
+        self.inner
+            .newtype_variant_seed(Wrap::new(seed, track))
+            .map_err(|e| track.fail(e))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let track = self.track;
+
+        self.inner
+            .tuple_variant(len, Wrap::new(visitor, track))
+            .map_err(|e| track.fail(e))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let track = self.track;
+
+        self.inner
+            .struct_variant(fields, Wrap::new(visitor, track))
+            .map_err(|e| track.fail(e))
+    }
+}
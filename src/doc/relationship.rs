@@ -1,6 +1,6 @@
 use std::iter::FromIterator;
 
-use doc::{Data, Identifier, Link};
+use doc::{Data, Identifier, Link, PrimaryData, Version};
 use value::{Key, Map};
 
 /// Represents a resource's relationship to another.
@@ -69,6 +69,24 @@ impl Relationship {
             _ext: (),
         }
     }
+
+    /// Returns the earliest version of the specification that supports each of the
+    /// members that are present in `self`.
+    pub fn required_version(&self) -> Version {
+        let data = match self.data {
+            Data::Member(ref data) => data.iter().map(Identifier::required_version).max(),
+            Data::Collection(ref data) => {
+                data.iter().map(Identifier::required_version).max()
+            }
+        };
+
+        self.links
+            .values()
+            .map(Link::required_version)
+            .chain(data)
+            .max()
+            .unwrap_or_default()
+    }
 }
 
 impl From<Option<Identifier>> for Relationship {
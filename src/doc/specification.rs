@@ -1,6 +1,7 @@
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
+use http::Uri;
 use serde::de::{Deserialize, Deserializer, Error as DeError};
 use serde::ser::{Serialize, Serializer};
 
@@ -15,6 +16,12 @@ use value::Map;
 /// [JSON API object]: https://goo.gl/hZUcEt
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct JsonApi {
+    /// URIs of the extensions that have been applied to the document. If this value of
+    /// this field is empty, it will not be serialized. Extensions were introduced in
+    /// version 1.1 of the specification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "serde_uris")]
+    pub ext: Vec<Uri>,
+
     /// Non-standard meta information. If this value of this field is empty, it will not
     /// be included if the object is serialized. For more information, check out the
     /// *[meta information]* section of the JSON API specification.
@@ -23,8 +30,14 @@ pub struct JsonApi {
     #[serde(default, skip_serializing_if = "Map::is_empty")]
     pub meta: Map,
 
+    /// URIs of the profiles that have been applied to the document. If this value of
+    /// this field is empty, it will not be serialized. Profiles were introduced in
+    /// version 1.1 of the specification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "serde_uris")]
+    pub profile: Vec<Uri>,
+
     /// The latest version of the JSON API specification that is supported by
-    /// this implementation. Defaults to the latest available version.
+    /// this implementation. Defaults to version 1.0.
     pub version: Version,
 
     /// Private field for backwards compatibility.
@@ -48,10 +61,46 @@ impl JsonApi {
     pub fn new(version: Version) -> Self {
         JsonApi {
             version,
+            ext: Default::default(),
             meta: Default::default(),
+            profile: Default::default(),
             _ext: (),
         }
     }
+
+    /// Returns the earliest version of the specification that supports each of the
+    /// members that are present in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{JsonApi, Version};
+    ///
+    /// let mut jsonapi = JsonApi::default();
+    /// assert_eq!(jsonapi.required_version(), Version::V1);
+    ///
+    /// jsonapi.ext.push("https://jsonapi.org/ext/atomic".parse()?);
+    /// assert_eq!(jsonapi.required_version(), Version::V1_1);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn required_version(&self) -> Version {
+        if self.ext.is_empty() && self.profile.is_empty() {
+            Version::V1
+        } else {
+            Version::V1_1
+        }
+    }
 }
 
 /// The version of the specification.
@@ -59,6 +108,18 @@ impl JsonApi {
 pub enum Version {
     /// Version 1.0
     V1,
+
+    /// Version 1.1
+    V1_1,
+}
+
+impl Version {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            Version::V1 => "1.0",
+            Version::V1_1 => "1.1",
+        }
+    }
 }
 
 impl Default for Version {
@@ -69,9 +130,7 @@ impl Default for Version {
 
 impl Display for Version {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.write_str(match *self {
-            Version::V1 => "1.0",
-        })
+        f.write_str(self.as_str())
     }
 }
 
@@ -81,6 +140,7 @@ impl FromStr for Version {
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         match value {
             "1.0" => Ok(Version::V1),
+            "1.1" => Ok(Version::V1_1),
             v => Err(Error::unsupported_version(v)),
         }
     }
@@ -101,8 +161,35 @@ impl Serialize for Version {
     where
         S: Serializer,
     {
-        serializer.serialize_str(match *self {
-            Version::V1 => "1.0",
-        })
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+mod serde_uris {
+    use http::Uri;
+    use serde::de::{Deserialize, Deserializer, Error};
+    use serde::ser::{SerializeSeq, Serializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Uri>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|value| value.parse().map_err(Error::custom))
+            .collect()
+    }
+
+    pub fn serialize<S>(value: &[Uri], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(value.len()))?;
+
+        for uri in value {
+            state.serialize_element(&uri.to_string())?;
+        }
+
+        state.end()
     }
 }
@@ -0,0 +1,328 @@
+//! Check documents against the requirements of the specification.
+//!
+//! A document that was parsed or rendered by this crate already satisfies many of the
+//! requirements of the specification by construction. The functions in this module
+//! check the requirements that cannot be expressed in the type system, such as *[full
+//! linkage]*. Each violation is returned as an [`ErrorObject`] with a JSON pointer to
+//! the member that caused it.
+//!
+//! [`ErrorObject`]: ../struct.ErrorObject.html
+//! [full linkage]: http://jsonapi.org/format/#document-compound-documents
+
+use serde_json::{self, Value as JsonValue};
+
+use doc::{Data, Document, ErrorObject, ErrorSource, Identifier, JsonApi, Link, Object,
+          PrimaryData, Relationship, Version};
+use error::Error;
+use value::{Key, Map, Set};
+
+const RESERVED: &[&str] = &["id", "type"];
+
+/// Validate a document from bytes of JSON text.
+///
+/// In addition to the requirements checked by [`Document::validate`], this function
+/// checks requirements of the top-level structure that a parsed [`Document`] cannot
+/// represent (i.e `data` and `errors` must not coexist). An error is only returned if
+/// the bytes are not valid JSON.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::validate;
+///
+/// let errors = validate::from_slice(br#"{
+///     "data": null,
+///     "errors": []
+/// }"#)?;
+///
+/// assert_eq!(errors.len(), 1);
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Document`]: ../enum.Document.html
+/// [`Document::validate`]: ../enum.Document.html#method.validate
+pub fn from_slice(data: &[u8]) -> Result<Vec<ErrorObject>, Error> {
+    Ok(from_json(serde_json::from_slice(data)?))
+}
+
+/// Validate a document from a string of JSON text.
+///
+/// See [`from_slice`] for more information.
+///
+/// [`from_slice`]: ./fn.from_slice.html
+pub fn from_str(data: &str) -> Result<Vec<ErrorObject>, Error> {
+    from_slice(data.as_bytes())
+}
+
+fn from_json(value: JsonValue) -> Vec<ErrorObject> {
+    let mut errors = Vec::new();
+
+    {
+        let top = match value.as_object() {
+            Some(top) => top,
+            None => {
+                let detail = "A document must be a JSON object.";
+                return vec![error("", "Invalid document", detail)];
+            }
+        };
+
+        if !top.contains_key("data") && !top.contains_key("errors") && !top.contains_key("meta")
+        {
+            let detail = "A document must contain at least one of `data`, `errors`, or `meta`.";
+            errors.push(error("", "Invalid document", detail));
+        }
+
+        if top.contains_key("data") && top.contains_key("errors") {
+            let detail = "The members `data` and `errors` must not coexist in a document.";
+            errors.push(error("", "Invalid document", detail));
+        }
+
+        if top.contains_key("included") && !top.contains_key("data") {
+            let detail = "A document that does not contain `data` must not contain `included`.";
+            errors.push(error("/included", "Invalid document", detail));
+        }
+
+        let version = top.get("jsonapi").and_then(|jsonapi| jsonapi.get("version"));
+
+        if let Some(version) = version {
+            if version.as_str().map_or(true, |v| v.parse::<Version>().is_err()) {
+                let detail = r#"The version of the specification must be "1.0" or "1.1"."#;
+                errors.push(error("/jsonapi/version", "Invalid version", detail));
+            }
+        }
+
+        let included = top.get("included").and_then(JsonValue::as_array);
+        let mut seen = Set::new();
+
+        for (index, item) in included.into_iter().flatten().enumerate() {
+            let kind = item.get("type").and_then(JsonValue::as_str);
+            let id = item.get("id").and_then(JsonValue::as_str);
+
+            if let (Some(kind), Some(id)) = (kind, id) {
+                if !seen.insert((kind.to_owned(), id.to_owned())) {
+                    let pointer = format!("/included/{}", index);
+                    errors.push(duplicate(&pointer, kind, id));
+                }
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return errors;
+    }
+
+    let is_linkage = |item: &JsonValue| {
+        item.as_object().map_or(false, |item| {
+            item.keys()
+                .all(|key| key == "id" || key == "lid" || key == "meta" || key == "type")
+        })
+    };
+
+    let is_linkage = match value.get("data") {
+        Some(&JsonValue::Array(ref items)) => !items.is_empty() && items.iter().all(is_linkage),
+        Some(item) => is_linkage(item),
+        None => false,
+    };
+
+    let is_meta = value.get("data").is_none() && value.get("errors").is_none();
+
+    let result = if is_meta {
+        // A document without `data` or `errors` cannot be represented by a `Document`,
+        // so its top-level members are checked as part of an empty error document.
+        serde_json::from_value::<MetaRepr>(value).map(|repr| {
+            let doc = Document::<Object>::Err {
+                errors: Vec::new(),
+                jsonapi: repr.jsonapi,
+                links: repr.links,
+                meta: repr.meta,
+            };
+
+            doc.validate()
+        })
+    } else if is_linkage {
+        serde_json::from_value::<Document<Identifier>>(value).map(|doc| doc.validate())
+    } else {
+        serde_json::from_value::<Document<Object>>(value).map(|doc| doc.validate())
+    };
+
+    result.unwrap_or_else(|e| vec![error("", "Invalid document", &e.to_string())])
+}
+
+/// The top-level members of a document that only contains meta information.
+#[derive(Deserialize)]
+struct MetaRepr {
+    #[serde(default)]
+    jsonapi: JsonApi,
+    #[serde(default)]
+    links: Map<Key, Link>,
+    #[serde(default)]
+    meta: Map,
+}
+
+pub(crate) fn document<T: PrimaryData>(doc: &Document<T>) -> Vec<ErrorObject> {
+    let mut state = State::default();
+    let declared = doc.jsonapi().version;
+
+    if doc.required_version() > declared {
+        let detail = format!(
+            "The document contains members that were introduced in a later version of \
+             the specification than the declared version {}.",
+            declared
+        );
+
+        state.errors.push(error("/jsonapi/version", "Invalid version", &detail));
+    }
+
+    if let Document::Ok {
+        ref data,
+        ref included,
+        ..
+    } = *doc
+    {
+        match *data {
+            Data::Member(ref item) => if let Some(ref item) = **item {
+                item.validate("/data", &mut state);
+            },
+            Data::Collection(ref items) => for (index, item) in items.iter().enumerate() {
+                item.validate(&format!("/data/{}", index), &mut state);
+            },
+        }
+
+        let mut reachable = state.linked.drain(..).collect::<Vec<_>>();
+
+        for (index, item) in included.iter().enumerate() {
+            item.validate(&format!("/included/{}", index), &mut state);
+        }
+
+        let index = included
+            .iter()
+            .map(|item| ((&item.kind, &item.id), item))
+            .collect::<Map<_, _>>();
+        let mut visited = Set::new();
+
+        while let Some(ident) = reachable.pop() {
+            if let Some(item) = index.get(&(&ident.kind, &ident.id)) {
+                if visited.insert((&item.kind, &item.id)) {
+                    reachable.extend(linkage(&item.relationships).into_iter().cloned());
+                }
+            }
+        }
+
+        for (index, item) in included.iter().enumerate() {
+            if !visited.contains(&(&item.kind, &item.id)) {
+                let pointer = format!("/included/{}", index);
+                let detail = format!(
+                    "The included resource of type \"{}\" with id \"{}\" is not \
+                     referenced by the primary data or another included resource.",
+                    item.kind, item.id
+                );
+
+                state.errors.push(error(&pointer, "Full linkage required", &detail));
+            }
+        }
+    }
+
+    state.errors
+}
+
+/// Accumulates the errors and resource linkage found while validating a document.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct State {
+    errors: Vec<ErrorObject>,
+    linked: Vec<Identifier>,
+    seen: Set<(Key, String)>,
+}
+
+impl State {
+    #[doc(hidden)]
+    pub fn ident(&mut self, ident: &Identifier) {
+        self.linked.push(ident.clone());
+    }
+
+    #[doc(hidden)]
+    pub fn resource(
+        &mut self,
+        pointer: &str,
+        kind: &Key,
+        id: Option<&str>,
+        attributes: &Map,
+        relationships: &Map<Key, Relationship>,
+    ) {
+        if let Some(id) = id {
+            if !self.seen.insert((kind.clone(), id.to_owned())) {
+                self.errors.push(duplicate(pointer, kind, id));
+            }
+        }
+
+        for key in attributes.keys() {
+            if RESERVED.contains(&&**key) {
+                let pointer = format!("{}/attributes/{}", pointer, key);
+                let detail = format!(r#"A resource cannot have an attribute named "{}"."#, key);
+
+                self.errors.push(error(&pointer, "Invalid member name", &detail));
+            }
+        }
+
+        for key in relationships.keys() {
+            let pointer = format!("{}/relationships/{}", pointer, key);
+
+            if RESERVED.contains(&&**key) {
+                let detail = format!(r#"A resource cannot have a relationship named "{}"."#, key);
+                self.errors.push(error(&pointer, "Invalid member name", &detail));
+            } else if attributes.contains_key(key) {
+                let detail = format!(
+                    r#"A resource cannot have an attribute and a relationship named "{}"."#,
+                    key
+                );
+
+                self.errors.push(error(&pointer, "Invalid member name", &detail));
+            }
+        }
+
+        self.linked.extend(linkage(relationships).into_iter().cloned());
+    }
+}
+
+fn linkage(relationships: &Map<Key, Relationship>) -> Vec<&Identifier> {
+    let mut idents = Vec::new();
+
+    for rel in relationships.values() {
+        match rel.data {
+            Data::Member(ref data) => idents.extend(data.iter()),
+            Data::Collection(ref data) => idents.extend(data),
+        }
+    }
+
+    idents
+}
+
+fn duplicate(pointer: &str, kind: &str, id: &str) -> ErrorObject {
+    let detail = format!(
+        r#"The resource of type "{}" with id "{}" appears more than once in the document."#,
+        kind, id
+    );
+
+    error(pointer, "Duplicate resource", &detail)
+}
+
+fn error(pointer: &str, title: &str, detail: &str) -> ErrorObject {
+    let mut error = ErrorObject::new(None);
+
+    error.detail = Some(detail.to_owned());
+    error.source = Some(ErrorSource::new(None, Some(pointer.to_owned())));
+    error.title = Some(title.to_owned());
+
+    error
+}
@@ -7,6 +7,8 @@ use http::uri::InvalidUri as InvalidUriError;
 use serde_json::Error as JsonError;
 use serde_qs::Error as QueryError;
 
+use value::fields::Violation;
+
 error_chain!{
     foreign_links {
         InvalidStatusCode(InvalidStatusCodeError);
@@ -17,9 +19,29 @@ error_chain!{
     }
 
     errors {
-        InvalidMemberName(name: String) {
-            description("TODO")
-            display("TODO")
+        IncludeNotAllowed(path: String) {
+            description("A relationship path cannot be included.")
+            display(r#"the relationship path "{}" cannot be included"#, path)
+        }
+
+        IncludeTooDeep(path: String, max: usize) {
+            description("A relationship path is deeper than the maximum include depth.")
+            display(r#"the relationship path "{}" is deeper than {}"#, path, max)
+        }
+
+        InvalidDocument(pointer: String, detail: String) {
+            description("A document does not have the expected structure.")
+            display(r#"invalid document at "{}": {}"#, pointer, detail)
+        }
+
+        InvalidMemberName(name: String, violation: Violation) {
+            description("A member name does not conform to the specification.")
+            display(r#"invalid member name "{}": {}"#, name, violation)
+        }
+
+        InvalidParameter(parameter: String, detail: String) {
+            description("A query parameter cannot be used by the request.")
+            display(r#"invalid query parameter "{}": {}"#, parameter, detail)
         }
 
         MissingField(name: String) {
@@ -27,6 +49,11 @@ error_chain!{
             display(r#"missing required field "{}""#, name)
         }
 
+        TooManyIncluded(max: usize) {
+            description("A document includes more than the maximum number of resources.")
+            display("the document includes more than {} resources", max)
+        }
+
         UnsupportedVersion(version: String) {
             description("The specified version of is not \
                          supported by this implementation.")
@@ -37,10 +64,34 @@ error_chain!{
 }
 
 impl Error {
+    pub fn include_not_allowed(path: &str) -> Self {
+        Self::from(ErrorKind::IncludeNotAllowed(path.to_owned()))
+    }
+
+    pub fn include_too_deep(path: &str, max: usize) -> Self {
+        Self::from(ErrorKind::IncludeTooDeep(path.to_owned(), max))
+    }
+
+    pub fn invalid_document(pointer: &str, detail: &str) -> Self {
+        Self::from(ErrorKind::InvalidDocument(pointer.to_owned(), detail.to_owned()))
+    }
+
+    pub fn invalid_member_name(name: &str, violation: Violation) -> Self {
+        Self::from(ErrorKind::InvalidMemberName(name.to_owned(), violation))
+    }
+
+    pub fn invalid_parameter(parameter: &str, detail: &str) -> Self {
+        Self::from(ErrorKind::InvalidParameter(parameter.to_owned(), detail.to_owned()))
+    }
+
     pub fn missing_field(name: &str) -> Self {
         Self::from(ErrorKind::MissingField(name.to_owned()))
     }
 
+    pub fn too_many_included(max: usize) -> Self {
+        Self::from(ErrorKind::TooManyIncluded(max))
+    }
+
     pub fn unsupported_version(version: &str) -> Self {
         Self::from(ErrorKind::UnsupportedVersion(version.to_owned()))
     }
@@ -7,6 +7,7 @@ extern crate percent_encoding;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
 extern crate serde_json;
 extern crate serde_qs;
 
@@ -22,13 +23,17 @@ mod sealed {
 
 pub mod doc;
 pub mod error;
+pub mod openapi;
 pub mod query;
+pub mod schema;
 pub mod value;
 pub mod view;
 
 #[doc(inline)]
 pub use doc::Document;
 #[doc(inline)]
+pub use doc::{FromNewObject, FromObject};
+#[doc(inline)]
 pub use doc::{from_doc, from_reader, from_slice, from_str};
 #[doc(inline)]
 pub use doc::{to_doc, to_string, to_string_pretty, to_vec, to_vec_pretty, to_writer,
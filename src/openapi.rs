@@ -0,0 +1,797 @@
+//! Generate an [OpenAPI 3] description of an API from the schema of its resources.
+//!
+//! The document that is generated by an [`OpenApi`] contains the envelope schemas of
+//! the JSON API specification, a schema for the resource objects and documents of each
+//! type of resource in a [`Registry`], and the standard endpoints of each type along with
+//! the query parameters that they accept. It can be written to a file from a build script
+//! in order to generate clients for the API.
+//!
+//! The type of an attribute is not part of a [`Schema`], so the schema of each
+//! attribute accepts any JSON value.
+//!
+//! [OpenAPI 3]: https://spec.openapis.org/oas/v3.0.3
+//! [`OpenApi`]: ./struct.OpenApi.html
+//! [`Registry`]: ../schema/struct.Registry.html
+//! [`Schema`]: ../schema/struct.Schema.html
+
+use serde_json::{self, Map as JsonMap, Value as JsonValue};
+
+use error::Error;
+use schema::{Registry, Relation, Schema};
+
+/// The media type of a JSON API document.
+const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+/// The version of the OpenAPI specification that is generated.
+const VERSION: &str = "3.0.3";
+
+/// Describes the API that is generated from a [`Registry`].
+///
+/// # Example
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+///
+/// # use json_api::Error;
+/// #
+/// struct Post {
+///     id: u64,
+///     title: String,
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.id;
+///     attrs title;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// use json_api::openapi::OpenApi;
+/// use json_api::schema::Registry;
+///
+/// let mut registry = Registry::new();
+/// registry.register::<Post>()?;
+///
+/// let mut openapi = OpenApi::new("Blog", "1.0.0");
+/// openapi.prefix = "/api".to_owned();
+///
+/// let spec = openapi.to_json(&registry);
+///
+/// assert_eq!(spec["info"]["title"], "Blog");
+/// assert!(spec["paths"]["/api/posts/{id}"]["patch"].is_object());
+/// assert!(spec["components"]["schemas"]["posts.Attributes"]["properties"]["title"]
+///     .is_object());
+///
+/// // Write the document to a file, e.g. from a build script.
+/// let _ = openapi.to_string_pretty(&registry)?;
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Registry`]: ../schema/struct.Registry.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpenApi {
+    /// The path that each endpoint starts with, e.g. `"/api"`. Defaults to an empty
+    /// string.
+    pub prefix: String,
+
+    /// The title of the API.
+    pub title: String,
+
+    /// The version of the API. This is not the version of the OpenAPI specification.
+    pub version: String,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl OpenApi {
+    /// Returns a new `OpenApi` with the given title and version.
+    pub fn new<T, V>(title: T, version: V) -> Self
+    where
+        T: Into<String>,
+        V: Into<String>,
+    {
+        OpenApi {
+            prefix: String::new(),
+            title: title.into(),
+            version: version.into(),
+            _ext: (),
+        }
+    }
+
+    /// Returns the OpenAPI document that describes each type of resource in
+    /// `registry`.
+    pub fn to_json(&self, registry: &Registry) -> JsonValue {
+        let mut paths = JsonMap::new();
+        let mut schemas = envelope();
+
+        for (_, schema) in registry.iter() {
+            self.paths(&mut paths, schema, registry);
+            resource(&mut schemas, schema, registry);
+        }
+
+        json!({
+            "openapi": VERSION,
+            "info": {
+                "title": self.title,
+                "version": self.version,
+            },
+            "paths": paths,
+            "components": {
+                "parameters": parameters(registry),
+                "responses": {
+                    "Error": {
+                        "description": "An error document.",
+                        "content": content(reference("ErrorDocument")),
+                    },
+                },
+                "schemas": schemas,
+            },
+        })
+    }
+
+    /// Returns the OpenAPI document that describes each type of resource in `registry`
+    /// as a string of JSON.
+    pub fn to_string(&self, registry: &Registry) -> Result<String, Error> {
+        Ok(serde_json::to_string(&self.to_json(registry))?)
+    }
+
+    /// Returns the OpenAPI document that describes each type of resource in `registry`
+    /// as a pretty-printed string of JSON.
+    pub fn to_string_pretty(&self, registry: &Registry) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(&self.to_json(registry))?)
+    }
+
+    /// Adds the endpoints of the resource described by `schema` to `paths`.
+    fn paths(
+        &self,
+        paths: &mut JsonMap<String, JsonValue>,
+        schema: &Schema,
+        registry: &Registry,
+    ) {
+        let kind = &*schema.kind;
+        let base = format!("{}/{}", self.prefix, kind);
+
+        paths.insert(
+            base.clone(),
+            json!({
+                "get": operation(
+                    kind,
+                    &format!("{}.list", kind),
+                    &["include", "fields", "sort", "page", "filter"],
+                    None,
+                    ("200", Some(reference(&format!("{}.CollectionDocument", kind)))),
+                ),
+                "post": operation(
+                    kind,
+                    &format!("{}.create", kind),
+                    &[],
+                    Some(reference(&format!("{}.NewDocument", kind))),
+                    ("201", Some(reference(&format!("{}.Document", kind)))),
+                ),
+            }),
+        );
+
+        paths.insert(
+            format!("{}/{{id}}", base),
+            json!({
+                "parameters": [parameter("id")],
+                "get": operation(
+                    kind,
+                    &format!("{}.get", kind),
+                    &["include", "fields"],
+                    None,
+                    ("200", Some(reference(&format!("{}.Document", kind)))),
+                ),
+                "patch": operation(
+                    kind,
+                    &format!("{}.update", kind),
+                    &[],
+                    Some(reference(&format!("{}.Document", kind))),
+                    ("200", Some(reference(&format!("{}.Document", kind)))),
+                ),
+                "delete": operation(
+                    kind,
+                    &format!("{}.delete", kind),
+                    &[],
+                    None,
+                    ("204", None),
+                ),
+            }),
+        );
+
+        for (name, relation) in &schema.relationships {
+            let id = format!("{}.{}", kind, name);
+            let linkage = relationship(relation);
+
+            let (query, related): (&[&str], _) = match *relation {
+                Relation::HasMany(ref target) => (
+                    &["include", "fields", "sort", "page", "filter"],
+                    document(json!({
+                        "type": "array",
+                        "items": object(target, registry),
+                    })),
+                ),
+                Relation::HasOne(ref target) => (
+                    &["include", "fields"],
+                    document(nullable(object(target, registry))),
+                ),
+            };
+
+            paths.insert(
+                format!("{}/{{id}}/{}", base, name),
+                json!({
+                    "parameters": [parameter("id")],
+                    "get": operation(
+                        kind,
+                        &format!("{}.get", id),
+                        query,
+                        None,
+                        ("200", Some(related)),
+                    ),
+                }),
+            );
+
+            let mut methods = JsonMap::new();
+
+            methods.insert("parameters".to_owned(), json!([parameter("id")]));
+            methods.insert(
+                "get".to_owned(),
+                operation(
+                    kind,
+                    &format!("{}.relationship.get", id),
+                    &[],
+                    None,
+                    ("200", Some(linkage.clone())),
+                ),
+            );
+            methods.insert(
+                "patch".to_owned(),
+                operation(
+                    kind,
+                    &format!("{}.relationship.update", id),
+                    &[],
+                    Some(linkage.clone()),
+                    ("204", None),
+                ),
+            );
+
+            if relation.is_many() {
+                methods.insert(
+                    "post".to_owned(),
+                    operation(
+                        kind,
+                        &format!("{}.relationship.add", id),
+                        &[],
+                        Some(linkage.clone()),
+                        ("204", None),
+                    ),
+                );
+                methods.insert(
+                    "delete".to_owned(),
+                    operation(
+                        kind,
+                        &format!("{}.relationship.remove", id),
+                        &[],
+                        Some(linkage),
+                        ("204", None),
+                    ),
+                );
+            }
+
+            paths.insert(
+                format!("{}/{{id}}/relationships/{}", base, name),
+                JsonValue::Object(methods),
+            );
+        }
+    }
+}
+
+/// Returns the schemas that are defined by the JSON API specification.
+fn envelope() -> JsonMap<String, JsonValue> {
+    let mut schemas = JsonMap::new();
+
+    // OpenAPI 3.0 does not have a null type and `nullable` has no effect next to
+    // `oneOf` or `$ref`, so a member that can be null references this schema instead.
+    schemas.insert(
+        "Null".to_owned(),
+        json!({
+            "type": "object",
+            "nullable": true,
+            "enum": [null],
+        }),
+    );
+
+    schemas.insert(
+        "Meta".to_owned(),
+        json!({
+            "type": "object",
+            "additionalProperties": true,
+        }),
+    );
+
+    schemas.insert(
+        "Link".to_owned(),
+        json!({
+            "oneOf": [
+                {
+                    "type": "string",
+                    "format": "uri-reference",
+                },
+                {
+                    "type": "object",
+                    "required": ["href"],
+                    "properties": {
+                        "href": {
+                            "type": "string",
+                            "format": "uri-reference",
+                        },
+                        "meta": reference("Meta"),
+                    },
+                },
+            ],
+        }),
+    );
+
+    schemas.insert(
+        "Links".to_owned(),
+        json!({
+            "type": "object",
+            "additionalProperties": reference("Link"),
+        }),
+    );
+
+    schemas.insert(
+        "JsonApi".to_owned(),
+        json!({
+            "type": "object",
+            "properties": {
+                "version": { "type": "string" },
+                "meta": reference("Meta"),
+            },
+        }),
+    );
+
+    schemas.insert(
+        "Identifier".to_owned(),
+        json!({
+            "type": "object",
+            "required": ["type"],
+            "anyOf": [
+                { "required": ["id"] },
+                { "required": ["lid"] },
+            ],
+            "properties": {
+                "type": { "type": "string" },
+                "id": { "type": "string" },
+                "lid": { "type": "string" },
+                "meta": reference("Meta"),
+            },
+        }),
+    );
+
+    schemas.insert(
+        "Relationship".to_owned(),
+        json!({
+            "type": "object",
+            "properties": {
+                "data": {
+                    "oneOf": [
+                        reference("Identifier"),
+                        {
+                            "type": "array",
+                            "items": reference("Identifier"),
+                        },
+                        reference("Null"),
+                    ],
+                },
+                "links": reference("Links"),
+                "meta": reference("Meta"),
+            },
+        }),
+    );
+
+    schemas.insert(
+        "Object".to_owned(),
+        object_schema(
+            json!({ "type": "string" }),
+            json!({
+                "type": "object",
+                "additionalProperties": true,
+            }),
+            json!({
+                "type": "object",
+                "additionalProperties": reference("Relationship"),
+            }),
+            true,
+        ),
+    );
+
+    schemas.insert(
+        "NewObject".to_owned(),
+        object_schema(
+            json!({ "type": "string" }),
+            json!({
+                "type": "object",
+                "additionalProperties": true,
+            }),
+            json!({
+                "type": "object",
+                "additionalProperties": reference("Relationship"),
+            }),
+            false,
+        ),
+    );
+
+    schemas.insert(
+        "Document".to_owned(),
+        document(json!({
+            "oneOf": [
+                reference("Object"),
+                {
+                    "type": "array",
+                    "items": reference("Object"),
+                },
+                reference("Null"),
+            ],
+        })),
+    );
+
+    schemas.insert(
+        "ErrorObject".to_owned(),
+        json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "links": reference("Links"),
+                "status": { "type": "string" },
+                "code": { "type": "string" },
+                "title": { "type": "string" },
+                "detail": { "type": "string" },
+                "source": {
+                    "type": "object",
+                    "properties": {
+                        "pointer": { "type": "string" },
+                        "parameter": { "type": "string" },
+                    },
+                },
+                "meta": reference("Meta"),
+            },
+        }),
+    );
+
+    schemas.insert(
+        "ErrorDocument".to_owned(),
+        json!({
+            "type": "object",
+            "required": ["errors"],
+            "properties": {
+                "errors": {
+                    "type": "array",
+                    "items": reference("ErrorObject"),
+                },
+                "jsonapi": reference("JsonApi"),
+                "links": reference("Links"),
+                "meta": reference("Meta"),
+            },
+        }),
+    );
+
+    schemas
+}
+
+/// Adds the schemas of the resource described by `schema` to `schemas`.
+fn resource(
+    schemas: &mut JsonMap<String, JsonValue>,
+    schema: &Schema,
+    registry: &Registry,
+) {
+    let kind = &*schema.kind;
+    let name = |suffix: &str| format!("{}.{}", kind, suffix);
+    let kind_schema = json!({
+        "type": "string",
+        "enum": [kind],
+    });
+
+    let attributes = schema
+        .attributes
+        .iter()
+        .map(|key| (key.to_string(), json!({})))
+        .collect::<JsonMap<_, _>>();
+
+    let relationships = schema
+        .relationships
+        .iter()
+        .map(|(key, relation)| (key.to_string(), relationship(relation)))
+        .collect::<JsonMap<_, _>>();
+
+    schemas.insert(
+        name("Attributes"),
+        json!({
+            "type": "object",
+            "properties": attributes,
+        }),
+    );
+
+    schemas.insert(
+        name("Relationships"),
+        json!({
+            "type": "object",
+            "properties": relationships,
+        }),
+    );
+
+    schemas.insert(
+        name("Object"),
+        object_schema(
+            kind_schema.clone(),
+            reference(&name("Attributes")),
+            reference(&name("Relationships")),
+            true,
+        ),
+    );
+
+    schemas.insert(
+        name("NewObject"),
+        object_schema(
+            kind_schema,
+            reference(&name("Attributes")),
+            reference(&name("Relationships")),
+            false,
+        ),
+    );
+
+    schemas.insert(name("Document"), document(object(&schema.kind, registry)));
+    schemas.insert(name("NewDocument"), document(reference(&name("NewObject"))));
+    schemas.insert(
+        name("CollectionDocument"),
+        document(json!({
+            "type": "array",
+            "items": object(&schema.kind, registry),
+        })),
+    );
+}
+
+/// Returns the shared query and path parameters of each endpoint.
+fn parameters(registry: &Registry) -> JsonValue {
+    let fields = registry
+        .iter()
+        .map(|(kind, _)| {
+            let description =
+                format!(r#"A comma-separated list of the fields of "{}"."#, kind);
+            let schema = json!({
+                "type": "string",
+                "description": description,
+            });
+
+            (kind.to_string(), schema)
+        })
+        .collect::<JsonMap<_, _>>();
+
+    json!({
+        "id": {
+            "name": "id",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "string" },
+        },
+        "include": {
+            "name": "include",
+            "in": "query",
+            "description": "A comma-separated list of relationship paths to include.",
+            "schema": { "type": "string" },
+        },
+        "fields": {
+            "name": "fields",
+            "in": "query",
+            "description": "The sparse fieldset of each type of resource.",
+            "style": "deepObject",
+            "explode": true,
+            "schema": {
+                "type": "object",
+                "properties": fields,
+            },
+        },
+        "sort": {
+            "name": "sort",
+            "in": "query",
+            "description": "A comma-separated list of fields to sort by. A field that \
+                            starts with \"-\" is sorted in descending order.",
+            "schema": { "type": "string" },
+        },
+        "page": {
+            "name": "page",
+            "in": "query",
+            "description": "Page number, offset, or cursor based pagination.",
+            "style": "deepObject",
+            "explode": true,
+            "schema": {
+                "type": "object",
+                "properties": {
+                    "after": { "type": "string" },
+                    "before": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 0 },
+                    "number": { "type": "integer", "minimum": 1 },
+                    "offset": { "type": "integer", "minimum": 0 },
+                    "size": { "type": "integer", "minimum": 0 },
+                },
+            },
+        },
+        "filter": {
+            "name": "filter",
+            "in": "query",
+            "description": "Implementation-specific filters, keyed by field path.",
+            "style": "deepObject",
+            "explode": true,
+            "schema": {
+                "type": "object",
+                "additionalProperties": { "type": "string" },
+            },
+        },
+    })
+}
+
+/// Returns an operation of the resource `kind`.
+fn operation(
+    kind: &str,
+    id: &str,
+    query: &[&str],
+    body: Option<JsonValue>,
+    (status, success): (&str, Option<JsonValue>),
+) -> JsonValue {
+    let mut value = JsonMap::new();
+    let mut responses = JsonMap::new();
+
+    value.insert("operationId".to_owned(), id.into());
+    value.insert("tags".to_owned(), json!([kind]));
+
+    if !query.is_empty() {
+        let query = query.iter().map(|name| parameter(name)).collect();
+        value.insert("parameters".to_owned(), JsonValue::Array(query));
+    }
+
+    if let Some(body) = body {
+        let body = json!({
+            "required": true,
+            "content": content(body),
+        });
+
+        value.insert("requestBody".to_owned(), body);
+    }
+
+    responses.insert(status.to_owned(), {
+        let mut response = JsonMap::new();
+
+        response.insert("description".to_owned(), "Success.".into());
+
+        if let Some(success) = success {
+            response.insert("content".to_owned(), content(success));
+        }
+
+        JsonValue::Object(response)
+    });
+
+    responses.insert(
+        "default".to_owned(),
+        json!({ "$ref": "#/components/responses/Error" }),
+    );
+
+    value.insert("responses".to_owned(), JsonValue::Object(responses));
+    JsonValue::Object(value)
+}
+
+/// Returns the schema of a resource object. The id is only required if `id` is `true`.
+fn object_schema(
+    kind: JsonValue,
+    attributes: JsonValue,
+    relationships: JsonValue,
+    id: bool,
+) -> JsonValue {
+    let required = if id { json!(["type", "id"]) } else { json!(["type"]) };
+
+    json!({
+        "type": "object",
+        "required": required,
+        "properties": {
+            "type": kind,
+            "id": { "type": "string" },
+            "attributes": attributes,
+            "relationships": relationships,
+            "links": reference("Links"),
+            "meta": reference("Meta"),
+        },
+    })
+}
+
+/// Returns the schema of a document with `data` as primary data.
+fn document(data: JsonValue) -> JsonValue {
+    json!({
+        "type": "object",
+        "required": ["data"],
+        "properties": {
+            "data": data,
+            "included": {
+                "type": "array",
+                "items": reference("Object"),
+            },
+            "jsonapi": reference("JsonApi"),
+            "links": reference("Links"),
+            "meta": reference("Meta"),
+        },
+    })
+}
+
+/// Returns the schema of a relationship object that describes `relation`.
+fn relationship(relation: &Relation) -> JsonValue {
+    let ident = json!({
+        "allOf": [
+            reference("Identifier"),
+            {
+                "type": "object",
+                "properties": {
+                    "type": {
+                        "type": "string",
+                        "enum": [relation.kind()],
+                    },
+                },
+            },
+        ],
+    });
+
+    let data = if relation.is_many() {
+        json!({
+            "type": "array",
+            "items": ident,
+        })
+    } else {
+        nullable(ident)
+    };
+
+    json!({
+        "type": "object",
+        "properties": {
+            "data": data,
+            "links": reference("Links"),
+            "meta": reference("Meta"),
+        },
+    })
+}
+
+/// Returns a reference to the schema of a resource object of the type `kind`. If the
+/// type is not in `registry`, the generic `Object` schema is used instead.
+fn object(kind: &str, registry: &Registry) -> JsonValue {
+    match registry.get(kind) {
+        Some(_) => reference(&format!("{}.Object", kind)),
+        None => reference("Object"),
+    }
+}
+
+/// Returns a map of the media type of a JSON API document to `schema`.
+fn content(schema: JsonValue) -> JsonValue {
+    let mut content = JsonMap::new();
+
+    content.insert(MEDIA_TYPE.to_owned(), json!({ "schema": schema }));
+    JsonValue::Object(content)
+}
+
+/// Returns a schema that matches `schema` or `null`.
+fn nullable(schema: JsonValue) -> JsonValue {
+    json!({ "oneOf": [schema, reference("Null")] })
+}
+
+/// Returns a reference to the parameter `name`.
+fn parameter(name: &str) -> JsonValue {
+    json!({ "$ref": format!("#/components/parameters/{}", name) })
+}
+
+/// Returns a reference to the schema `name`.
+fn reference(name: &str) -> JsonValue {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
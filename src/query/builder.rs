@@ -1,17 +1,18 @@
 use std::mem;
 
 use error::Error;
-use query::{Direction, Page, Query, Sort};
+use query::{Cursor, Direction, Offset, Page, Pagination, Query, Sort, RESERVED};
 use value::{Key, Map, Path, Set, Value};
 
 /// An implementation of the "builder pattern" that can be used to construct a
 /// new query.
 #[derive(Default)]
 pub struct Builder {
+    extra: Vec<(String, Value)>,
     fields: Vec<(String, Vec<String>)>,
     filter: Vec<(String, Value)>,
     include: Vec<String>,
-    page: Option<Page>,
+    page: Option<Pagination>,
     sort: Vec<(String, Direction)>,
 }
 
@@ -55,10 +56,33 @@ impl Builder {
                     .collect::<Result<Set<Path>, Error>>()?
             },
             page: mem::replace(&mut self.page, None),
+            extra: {
+                self.extra
+                    .drain(..)
+                    .map(|(key, value)| {
+                        if RESERVED.contains(&key.as_str()) {
+                            bail!("'{}' is a well-known query parameter", key);
+                        }
+
+                        Ok((key, value))
+                    })
+                    .collect::<Result<Map<String, Value>, Error>>()?
+            },
             _ext: (),
         })
     }
 
+    /// Add an implementation-specific query parameter. Building the query will fail
+    /// if `key` is the name of a well-known query parameter.
+    pub fn extra<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        self.extra.push((key.into(), value.into()));
+        self
+    }
+
     pub fn fields<I, K, V>(&mut self, key: K, iter: I) -> &mut Self
     where
         I: IntoIterator<Item = V>,
@@ -92,8 +116,52 @@ impl Builder {
         self
     }
 
+    /// Use offset and limit based pagination.
+    pub fn offset(&mut self, offset: u64, limit: Option<u64>) -> &mut Self {
+        self.page = Some(Offset::new(offset, limit).into());
+        self
+    }
+
     pub fn page(&mut self, number: u64, size: Option<u64>) -> &mut Self {
-        self.page = Some(Page::new(number, size));
+        self.page = Some(Page::new(number, size).into());
+        self
+    }
+
+    /// Use cursor based pagination and request the items that come after `cursor`.
+    ///
+    /// This method can be combined with [`before`]. The `size` argument of the last
+    /// call is used as the page size.
+    ///
+    /// [`before`]: #method.before
+    pub fn after<V>(&mut self, cursor: V, size: Option<u64>) -> &mut Self
+    where
+        V: Into<String>,
+    {
+        let mut value = self.cursor();
+
+        value.after = Some(cursor.into());
+        value.size = size;
+
+        self.page = Some(value.into());
+        self
+    }
+
+    /// Use cursor based pagination and request the items that come before `cursor`.
+    ///
+    /// This method can be combined with [`after`]. The `size` argument of the last
+    /// call is used as the page size.
+    ///
+    /// [`after`]: #method.after
+    pub fn before<V>(&mut self, cursor: V, size: Option<u64>) -> &mut Self
+    where
+        V: Into<String>,
+    {
+        let mut value = self.cursor();
+
+        value.before = Some(cursor.into());
+        value.size = size;
+
+        self.page = Some(value.into());
         self
     }
 
@@ -104,4 +172,11 @@ impl Builder {
         self.sort.push((field.into(), direction));
         self
     }
+
+    fn cursor(&mut self) -> Cursor {
+        match self.page.take() {
+            Some(Pagination::Cursor(cursor)) => cursor,
+            _ => Default::default(),
+        }
+    }
 }
@@ -0,0 +1,175 @@
+use serde::de::DeserializeOwned;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json;
+
+use error::Error;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Cursor based pagination parameters.
+///
+/// A cursor is an opaque string that points to a position in a collection. The
+/// `page[after]` and `page[before]` parameters request the items that come after or
+/// before a given cursor. For more information, check out the *[cursor pagination
+/// profile]*.
+///
+/// [cursor pagination profile]: https://jsonapi.org/profiles/ethanresnick/cursor-pagination/
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Cursor {
+    /// Requests the items that come after this cursor.
+    pub after: Option<String>,
+
+    /// Requests the items that come before this cursor.
+    pub before: Option<String>,
+
+    /// Optionally specifies the maximum number of items to include per page.
+    pub size: Option<u64>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Cursor {
+    /// Returns a new `Cursor`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::query::Cursor;
+    /// assert_eq!(Cursor::new(None, None, None), Cursor::default());
+    /// # }
+    /// ```
+    pub fn new(after: Option<String>, before: Option<String>, size: Option<u64>) -> Self {
+        Cursor {
+            after,
+            before,
+            size,
+            _ext: (),
+        }
+    }
+
+    /// Encodes `value` as an opaque cursor that can be used in a query string.
+    ///
+    /// The value is serialized as JSON and encoded with the URL safe base64 alphabet.
+    /// Use [`decode`] to get the original value back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::query::Cursor;
+    ///
+    /// let cursor = Cursor::encode(&("2018-01-01", 25))?;
+    /// let value: (String, u64) = Cursor::decode(&cursor)?;
+    ///
+    /// assert_eq!(value, ("2018-01-01".to_owned(), 25));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`decode`]: #method.decode
+    pub fn encode<T: Serialize>(value: &T) -> Result<String, Error> {
+        let data = serde_json::to_vec(value)?;
+        let chunks = data.len() / 3 + (data.len() % 3 != 0) as usize;
+        let mut output = String::with_capacity(chunks * 4);
+
+        for chunk in data.chunks(3) {
+            let bits = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |acc, (idx, byte)| acc | u32::from(*byte) << (16 - idx * 8));
+
+            for idx in 0..=chunk.len() {
+                let value = (bits >> (18 - idx * 6)) & 0x3f;
+                output.push(char::from(ALPHABET[value as usize]));
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Decodes a cursor that was created with [`encode`].
+    ///
+    /// [`encode`]: #method.encode
+    pub fn decode<T: DeserializeOwned>(value: &str) -> Result<T, Error> {
+        let mut data = Vec::with_capacity(value.len() / 4 * 3 + 2);
+
+        for chunk in value.as_bytes().chunks(4) {
+            if chunk.len() < 2 {
+                bail!("invalid cursor");
+            }
+
+            let mut bits = 0u32;
+
+            for (idx, byte) in chunk.iter().enumerate() {
+                let value = match ALPHABET.iter().position(|item| item == byte) {
+                    Some(value) => value as u32,
+                    None => bail!("invalid cursor"),
+                };
+
+                bits |= value << (18 - idx * 6);
+            }
+
+            for idx in 0..chunk.len() - 1 {
+                data.push((bits >> (16 - idx * 8)) as u8);
+            }
+        }
+
+        Ok(serde_json::from_slice(&data)?)
+    }
+}
+
+impl Serialize for Cursor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Cursor", 3)?;
+
+        if let Some(ref value) = self.after {
+            state.serialize_field("after", value)?;
+        }
+
+        if let Some(ref value) = self.before {
+            state.serialize_field("before", value)?;
+        }
+
+        if let Some(ref value) = self.size {
+            state.serialize_field("size", value)?;
+        }
+
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cursor;
+
+    #[test]
+    fn cursor_round_trip() {
+        let values = vec!["", "a", "ab", "abc", "abcd", "2018-01-01T00:00:00Z\u{1f980}"];
+
+        for value in values {
+            let cursor = Cursor::encode(&value).unwrap();
+
+            assert!(!cursor.contains('='));
+            assert_eq!(Cursor::decode::<String>(&cursor).unwrap(), value);
+        }
+
+        assert!(Cursor::decode::<String>("not a cursor").is_err());
+        assert!(Cursor::decode::<String>("IjEi5").is_err());
+    }
+}
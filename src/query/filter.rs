@@ -0,0 +1,397 @@
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use serde::ser::{Serialize, Serializer};
+
+use error::Error;
+use value::{Key, Map, Path, Value};
+
+/// A structured filter expression.
+///
+/// The JSON API specification reserves the `filter` query parameter but does not
+/// specify how it is used. A `Filter` can be parsed from the raw [`Query::filter`] map
+/// when a query string uses the following conventions:
+///
+/// | Query string                           | Expression                         |
+/// |----------------------------------------|------------------------------------|
+/// | `filter[name]=Dan`                     | `name` is equal to `Dan`           |
+/// | `filter[id]=1,2,3`                     | `id` is one of `1`, `2`, or `3`    |
+/// | `filter[age][gt]=21`                   | `age` is greater than `21`         |
+/// | `filter[name][like]=Da*`               | `name` matches the pattern `Da*`   |
+/// | `filter[id][in][]=1`                   | `id` is one of `1`                 |
+/// | `filter[age][not][gt]=21`              | `age` is not greater than `21`     |
+/// | `filter[users.name]=Dan`               | `users.name` is equal to `Dan`     |
+/// | `filter[not][name]=Dan`                | the group is not true              |
+/// | `filter[or][0][name]=Dan&filter[or][1][age]=21` | any group is true         |
+/// | `filter[and][0][age][gt]=21&filter[and][1][age][lt]=65` | every group is true |
+///
+/// Multiple parameters are combined with `and`. Within a group, a field path is
+/// written as nested member names (i.e `filter[or][0][users][name]=Dan`) because a
+/// member name cannot contain a `.`. A member name that is the name of an
+/// [`Operator`], `in`, or `not` is always interpreted as such.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, Filter, Operator};
+///
+/// let query = query::from_str("filter[age][gt]=21&filter[id]=1,2,3")?;
+/// let filter = Filter::from_map(&query.filter)?;
+///
+/// assert_eq!(filter, Filter::And(vec![
+///     Filter::Compare("age".parse()?, Operator::Gt, "21".into()),
+///     Filter::In("id".parse()?, vec!["1".into(), "2".into(), "3".into()]),
+/// ]));
+///
+/// assert_eq!(filter.to_map(), query.filter);
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Operator`]: ./enum.Operator.html
+/// [`Query::filter`]: ./struct.Query.html#structfield.filter
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    /// True if every filter is true.
+    And(Vec<Filter>),
+
+    /// True if the value of the field compares to the value with the operator.
+    Compare(Path, Operator, Value),
+
+    /// True if the value of the field is equal to one of the values.
+    In(Path, Vec<Value>),
+
+    /// True if the filter is false.
+    Not(Box<Filter>),
+
+    /// True if at least one filter is true.
+    Or(Vec<Filter>),
+}
+
+impl Filter {
+    /// Parses a `Filter` from the filter parameters of a query.
+    ///
+    /// An error is returned if a group is not an object or a list of objects, or if
+    /// the value of an operator is not valid.
+    pub fn from_map(map: &Map<Path, Value>) -> Result<Self, Error> {
+        map.iter()
+            .map(|(path, value)| entry(path.clone(), value))
+            .collect::<Result<_, _>>()
+            .map(all)
+    }
+
+    /// Returns the filter parameters of a query that are equivalent to `self`.
+    pub fn to_map(&self) -> Map<Path, Value> {
+        let entries = self.entries();
+        let mut map = Map::with_capacity(entries.len());
+
+        for (path, value) in entries {
+            let reserved = path.len() == 1 && is_reserved(&path[0]);
+
+            if !insert(&mut map, path, value, reserved) {
+                let (path, value) = self.group();
+
+                map.clear();
+                map.insert(path, value);
+                break;
+            }
+        }
+
+        map
+    }
+
+    /// Returns the filter parameters of `self` without merging the parameters of the
+    /// filters in an `And` expression.
+    fn entries(&self) -> Vec<(Path, Value)> {
+        match *self {
+            Filter::And(ref filters) => {
+                filters.iter().flat_map(Filter::entries).collect()
+            }
+            Filter::Compare(ref path, Operator::Eq, ref value) if !is_list(value) => {
+                vec![(path.clone(), value.clone())]
+            }
+            Filter::Compare(ref path, op, ref value) => {
+                vec![(path.clone(), single(op.as_str(), value.clone()))]
+            }
+            Filter::In(ref path, ref values) => {
+                let value = if values.len() > 1 && values.iter().all(is_item) {
+                    let items = values.iter().filter_map(Value::as_str);
+                    Value::String(items.collect::<Vec<_>>().join(","))
+                } else {
+                    single("in", Value::Array(values.clone()))
+                };
+
+                vec![(path.clone(), value)]
+            }
+            Filter::Not(ref filter) => {
+                vec![(reserved("not"), Value::Object(filter.object()))]
+            }
+            Filter::Or(_) => vec![self.group()],
+        }
+    }
+
+    /// Returns the filter parameters of `self` as a group of objects.
+    fn group(&self) -> (Path, Value) {
+        let (name, filters) = match *self {
+            Filter::And(ref filters) => ("and", filters.iter().collect()),
+            Filter::Or(ref filters) => ("or", filters.iter().collect()),
+            _ => ("and", vec![self]),
+        };
+
+        let items = filters.into_iter().map(|filter| Value::Object(filter.object()));
+        (reserved(name), items.collect())
+    }
+
+    /// Returns the filter parameters of `self` as an object that can be a member of a
+    /// group. The keys of a field path are nested objects.
+    fn object(&self) -> Map {
+        let mut map = Map::new();
+
+        for (path, value) in self.to_map() {
+            let mut keys = path.iter();
+            let key = match keys.next() {
+                Some(key) => key.clone(),
+                None => continue,
+            };
+
+            let value = keys.rev().fold(value, |value, key| single(key, value));
+            let reserved = path.len() == 1 && is_reserved(&key);
+
+            if !insert(&mut map, key, value, reserved) {
+                let (path, value) = self.group();
+
+                map.clear();
+                map.insert(path[0].clone(), value);
+                break;
+            }
+        }
+
+        map
+    }
+}
+
+impl Serialize for Filter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_map().serialize(serializer)
+    }
+}
+
+/// A comparison operator of a filter expression.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Operator {
+    /// Equal to (`eq`).
+    Eq,
+
+    /// Greater than (`gt`).
+    Gt,
+
+    /// Greater than or equal to (`gte`).
+    Gte,
+
+    /// Matches a pattern where `*` matches any sequence of characters (`like`).
+    Like,
+
+    /// Less than (`lt`).
+    Lt,
+
+    /// Less than or equal to (`lte`).
+    Lte,
+
+    /// Not equal to (`ne`).
+    Ne,
+}
+
+impl Operator {
+    /// Returns the name of the operator as it appears in a query string.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Operator::Eq => "eq",
+            Operator::Gt => "gt",
+            Operator::Gte => "gte",
+            Operator::Like => "like",
+            Operator::Lt => "lt",
+            Operator::Lte => "lte",
+            Operator::Ne => "ne",
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Operator {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "eq" => Ok(Operator::Eq),
+            "gt" => Ok(Operator::Gt),
+            "gte" => Ok(Operator::Gte),
+            "like" => Ok(Operator::Like),
+            "lt" => Ok(Operator::Lt),
+            "lte" => Ok(Operator::Lte),
+            "ne" => Ok(Operator::Ne),
+            _ => bail!("unknown filter operator '{}'", value),
+        }
+    }
+}
+
+fn all(mut filters: Vec<Filter>) -> Filter {
+    if filters.len() == 1 {
+        filters.remove(0)
+    } else {
+        Filter::And(filters)
+    }
+}
+
+fn entry(path: Path, value: &Value) -> Result<Filter, Error> {
+    let name = match path.len() {
+        1 => &*path[0],
+        _ => "",
+    };
+
+    match name {
+        "and" => group(value).map(Filter::And),
+        "not" => group(value).map(all).map(Box::new).map(Filter::Not),
+        "or" => group(value).map(Filter::Or),
+        _ => field(path, value),
+    }
+}
+
+fn field(path: Path, value: &Value) -> Result<Filter, Error> {
+    Ok(match *value {
+        Value::Array(ref items) => Filter::In(path, items.clone()),
+        Value::Object(ref map) => {
+            let mut filters = Vec::with_capacity(map.len());
+
+            for (key, value) in map {
+                filters.push(match &**key {
+                    "in" => Filter::In(path.clone(), list(value)),
+                    "not" => Filter::Not(Box::new(field(path.clone(), value)?)),
+                    name => match name.parse() {
+                        Ok(op) => Filter::Compare(path.clone(), op, value.clone()),
+                        Err(_) => {
+                            let mut path = path.clone();
+
+                            path.push(key.clone());
+                            field(path, value)?
+                        }
+                    },
+                });
+            }
+
+            all(filters)
+        }
+        ref value if is_list(value) => Filter::In(path, list(value)),
+        ref value => Filter::Compare(path, Operator::Eq, value.clone()),
+    })
+}
+
+fn group(value: &Value) -> Result<Vec<Filter>, Error> {
+    let object = |map: &Map| {
+        map.iter()
+            .map(|(key, value)| entry(Some(key.clone()).into_iter().collect(), value))
+            .collect::<Result<_, _>>()
+            .map(all)
+    };
+
+    match *value {
+        Value::Array(ref items) => items
+            .iter()
+            .map(|item| match *item {
+                Value::Object(ref map) => object(map),
+                _ => bail!("a filter group must be an object or a list of objects"),
+            })
+            .collect(),
+        Value::Object(ref map) => map.iter()
+            .map(|(key, value)| entry(Some(key.clone()).into_iter().collect(), value))
+            .collect(),
+        _ => bail!("a filter group must be an object or a list of objects"),
+    }
+}
+
+fn is_item(value: &Value) -> bool {
+    value.as_str().map_or(false, |item| !item.is_empty() && !item.contains(','))
+}
+
+fn is_list(value: &Value) -> bool {
+    value.as_str().map_or(false, |item| item.contains(','))
+}
+
+fn list(value: &Value) -> Vec<Value> {
+    match *value {
+        Value::Array(ref items) => items.clone(),
+        Value::String(ref item) => item.split(',').map(Value::from).collect(),
+        ref value => vec![value.clone()],
+    }
+}
+
+/// Inserts `value` into `map`. If `map` already contains `key`, both values are
+/// merged. Returns `false` if the values cannot be merged.
+fn insert<K>(map: &mut Map<K, Value>, key: K, value: Value, reserved: bool) -> bool
+where
+    K: Eq + Hash,
+{
+    if let Some(existing) = map.get_mut(&key) {
+        return !reserved && merge(existing, value);
+    }
+
+    map.insert(key, value);
+    true
+}
+
+fn is_reserved(key: &str) -> bool {
+    match key {
+        "and" | "in" | "not" | "or" => true,
+        _ => key.parse::<Operator>().is_ok(),
+    }
+}
+
+/// Merges `value` into `existing` if both are objects and the members that they have
+/// in common can be merged. Returns `false` if the values cannot be merged, in which
+/// case `existing` is not modified.
+fn merge(existing: &mut Value, value: Value) -> bool {
+    let mut merged = match (existing.as_object(), value) {
+        (Some(map), Value::Object(value)) => (map.clone(), value),
+        _ => return false,
+    };
+
+    for (key, value) in merged.1 {
+        let reserved = is_reserved(&key);
+
+        if !insert(&mut merged.0, key, value, reserved) {
+            return false;
+        }
+    }
+
+    *existing = Value::Object(merged.0);
+    true
+}
+
+fn reserved(name: &str) -> Path {
+    Some(Key::from_raw(name.to_owned())).into_iter().collect()
+}
+
+fn single(key: &str, value: Value) -> Value {
+    let mut map = Map::with_capacity(1);
+
+    map.insert(Key::from_raw(key.to_owned()), value);
+    Value::Object(map)
+}
@@ -0,0 +1,216 @@
+use http::Uri;
+
+use doc::Link;
+use error::Error;
+use query::{self, Cursor, Offset, Page, Pagination, Query};
+use value::{Key, Map};
+
+/// Returns the pagination links of a collection that contains `total` items.
+///
+/// The returned map contains the `self`, `first`, and `last` links. The `prev` and
+/// `next` links are only present if the current page is not the first or last page,
+/// respectively. Each link is `base` followed by the percent encoded `query` with the
+/// page number of the link. Any query string that is already present in `base` is
+/// replaced.
+///
+/// If the query uses offset and limit based pagination, the links contain the offset
+/// of each page instead of the page number.
+///
+/// If the query does not specify a page size, the collection is assumed to fit on a
+/// single page. A collection that does not contain any items has a single, empty page.
+/// An error is returned if the query uses cursor based pagination. Use
+/// [`cursor_links`] instead.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, Query};
+///
+/// let base = "https://example.com/articles".parse()?;
+/// let query = Query::builder().page(2, Some(10)).build()?;
+/// let links = query::page_links(&query, &base, 25)?;
+///
+/// let prev = "https://example.com/articles?page%5Bsize%5D=10";
+/// let last = "https://example.com/articles?page%5Bnumber%5D=3&page%5Bsize%5D=10";
+///
+/// assert_eq!(*links.get("prev").unwrap(), prev);
+/// assert_eq!(*links.get("last").unwrap(), last);
+/// assert_eq!(links.get("next"), links.get("last"));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`cursor_links`]: ./fn.cursor_links.html
+pub fn page_links(
+    query: &Query,
+    base: &Uri,
+    total: u64,
+) -> Result<Map<Key, Link>, Error> {
+    let page = match query.page {
+        Some(Pagination::Cursor(_)) => {
+            bail!("page links require page number or offset based pagination");
+        }
+        Some(Pagination::Offset(offset)) => {
+            return offset_links(query, base, offset, total);
+        }
+        Some(Pagination::Page(page)) => page,
+        None => Default::default(),
+    };
+    let last = match page.size {
+        Some(size) if size > 0 => (total / size + (total % size != 0) as u64).max(1),
+        _ => 1,
+    };
+
+    let mut links = Map::with_capacity(5);
+    let mut link = |key: &str, number: u64| -> Result<(), Error> {
+        let value = page_link(query, base, Page::new(number, page.size).into())?;
+
+        links.insert(Key::from_raw(key.to_owned()), value);
+        Ok(())
+    };
+
+    link("self", page.number)?;
+    link("first", 1)?;
+
+    if page.number > 1 {
+        link("prev", (page.number - 1).min(last))?;
+    }
+
+    if page.number < last {
+        link("next", page.number + 1)?;
+    }
+
+    link("last", last)?;
+    Ok(links)
+}
+
+fn offset_links(
+    query: &Query,
+    base: &Uri,
+    offset: Offset,
+    total: u64,
+) -> Result<Map<Key, Link>, Error> {
+    let current = offset.offset;
+    let last = match offset.limit {
+        Some(limit) if limit > 0 => total.saturating_sub(1) / limit * limit,
+        _ => 0,
+    };
+
+    let mut links = Map::with_capacity(5);
+    let mut link = |key: &str, value: u64| -> Result<(), Error> {
+        let value = page_link(query, base, Offset::new(value, offset.limit).into())?;
+
+        links.insert(Key::from_raw(key.to_owned()), value);
+        Ok(())
+    };
+
+    link("self", current)?;
+    link("first", 0)?;
+
+    if current > 0 {
+        let prev = current.saturating_sub(offset.limit.unwrap_or(current));
+        link("prev", prev.min(last))?;
+    }
+
+    if current < last {
+        link("next", current.saturating_add(offset.limit.unwrap_or(0)))?;
+    }
+
+    link("last", last)?;
+    Ok(links)
+}
+
+/// Returns the pagination links of a page in a collection that uses cursor based
+/// pagination.
+///
+/// The `prev` argument is the cursor of the first item in the page and should only be
+/// present if there are items before it. Likewise, the `next` argument is the cursor
+/// of the last item in the page and should only be present if there are items after
+/// it. The returned map contains the `self` and `first` links, as well as the `prev`
+/// and `next` links if the corresponding cursor is present. The page size of the
+/// query is preserved.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, Query};
+///
+/// let base = "https://example.com/articles".parse()?;
+/// let query = Query::builder().after("abc", Some(10)).build()?;
+/// let links = query::cursor_links(&query, &base, Some("def"), None)?;
+///
+/// let prev = "https://example.com/articles?page%5Bbefore%5D=def&page%5Bsize%5D=10";
+///
+/// assert_eq!(*links.get("prev").unwrap(), prev);
+/// assert!(links.get("next").is_none());
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+pub fn cursor_links(
+    query: &Query,
+    base: &Uri,
+    prev: Option<&str>,
+    next: Option<&str>,
+) -> Result<Map<Key, Link>, Error> {
+    let size = query.page.as_ref().and_then(Pagination::size);
+    let current = match query.page {
+        Some(Pagination::Cursor(ref cursor)) => cursor.clone(),
+        _ => Cursor::new(None, None, size),
+    };
+
+    let mut links = Map::with_capacity(4);
+    let mut link = |key: &str, cursor: Cursor| -> Result<(), Error> {
+        let value = page_link(query, base, cursor.into())?;
+
+        links.insert(Key::from_raw(key.to_owned()), value);
+        Ok(())
+    };
+
+    link("self", current)?;
+    link("first", Cursor::new(None, None, size))?;
+
+    if let Some(cursor) = prev {
+        link("prev", Cursor::new(None, Some(cursor.to_owned()), size))?;
+    }
+
+    if let Some(cursor) = next {
+        link("next", Cursor::new(Some(cursor.to_owned()), None, size))?;
+    }
+
+    Ok(links)
+}
+
+fn page_link(query: &Query, base: &Uri, page: Pagination) -> Result<Link, Error> {
+    let mut query = query.clone();
+    query.page = Some(page);
+
+    let base = base.to_string();
+    let path = base.split('?').next().unwrap_or_default();
+    let value = query::to_string(&query)?;
+
+    if value.is_empty() {
+        path.parse()
+    } else {
+        format!("{}?{}", path, value).parse()
+    }
+}
@@ -0,0 +1,386 @@
+use std::cmp::Ordering;
+
+use doc::{Data, Object};
+use error::Error;
+use query::{Direction, Filter, Operator, Pagination, Query};
+use resource::Resource;
+use value::{Path, Set, Value};
+use view::Context;
+
+/// Applies the filter, sort, and page parameters of `query` to a collection of
+/// resources held in memory.
+///
+/// Returns the resources in the requested page and the total number of resources that
+/// match the filter. See [`apply_objects`] for a description of how each parameter is
+/// applied.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// struct Post {
+///     id: u64,
+///     title: String,
+///     published: bool,
+/// }
+///
+/// resource!(Post, |&self| {
+///     kind "posts";
+///     id self.id;
+///     attrs title, published;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, Query};
+///
+/// let posts = (1..6)
+///     .map(|id| Post {
+///         id,
+///         title: format!("Post {}", id),
+///         published: id % 2 == 1,
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let query = Query::builder()
+///     .filter("published", true)
+///     .sort("id", query::Direction::Desc)
+///     .page(1, Some(2))
+///     .build()?;
+///
+/// let (page, total) = query::apply(&posts, &query)?;
+/// let ids = page.iter().map(|post| post.id).collect::<Vec<_>>();
+///
+/// assert_eq!(ids, vec![5, 3]);
+/// assert_eq!(total, 3);
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`apply_objects`]: ./fn.apply_objects.html
+pub fn apply<'a, T>(items: &'a [T], query: &Query) -> Result<(Vec<&'a T>, u64), Error>
+where
+    T: Resource,
+{
+    let mut included = Set::new();
+    let objects = items
+        .iter()
+        .map(|item| {
+            let mut ctx = Context::new(T::kind(), None, &mut included);
+            item.to_object(&mut ctx)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (page, total) = execute(&objects, query)?;
+    Ok((page.into_iter().map(|idx| &items[idx]).collect(), total))
+}
+
+/// Applies the filter, sort, and page parameters of `query` to a collection of
+/// resource objects held in memory.
+///
+/// Returns the resource objects in the requested page and the total number of resource
+/// objects that match the filter.
+///
+/// * The filter parameters are parsed with [`Filter::from_map`] and evaluated like
+///   the SQL clauses of [`sql::from_query`]. The string representation of a number or
+///   boolean is equal to the number or boolean, and is compared to a number as a
+///   number. A `like` pattern matches a string where `*` matches any sequence of
+///   characters. A field that is not present only matches a comparison with `null`.
+///   If the field is a to-many relationship, the resource object matches if the id of
+///   any related resource matches.
+/// * Resource objects are sorted by each sort instruction in order. Strings that are
+///   finite numbers (e.g. the id of a resource object) are compared as numbers and
+///   are sorted before any other string. A field that is not present is sorted before
+///   any other value.
+/// * The page parameters of page number or offset based pagination select the page.
+///
+/// A field path can refer to the `id` of a resource object, an attribute (or a member
+/// of an object nested in an attribute), or the id of a related resource. An error is
+/// returned if a filter parameter is not valid or the query uses cursor based
+/// pagination.
+///
+/// [`Filter::from_map`]: ./enum.Filter.html#method.from_map
+/// [`sql::from_query`]: ./sql/fn.from_query.html
+pub fn apply_objects(
+    items: Vec<Object>,
+    query: &Query,
+) -> Result<(Vec<Object>, u64), Error> {
+    let (page, total) = execute(&items, query)?;
+    let mut items = items.into_iter().map(Some).collect::<Vec<_>>();
+    let page = page.into_iter().filter_map(|idx| items[idx].take());
+
+    Ok((page.collect(), total))
+}
+
+fn execute(items: &[Object], query: &Query) -> Result<(Vec<usize>, u64), Error> {
+    let filter = Filter::from_map(&query.filter)
+        .map_err(|e| Error::invalid_parameter("filter", &e.to_string()))?;
+
+    let (offset, size) = match query.page {
+        Some(Pagination::Cursor(_)) => {
+            bail!("cursor based pagination cannot be applied to a collection in memory");
+        }
+        Some(ref page) => (page.offset().unwrap_or(0), page.size()),
+        None => (0, None),
+    };
+
+    let mut matches = Vec::new();
+
+    for (idx, item) in items.iter().enumerate() {
+        if eval(&filter, item)? {
+            matches.push(idx);
+        }
+    }
+
+    matches.sort_by(|a, b| {
+        query.sort.iter().fold(Ordering::Equal, |ordering, sort| {
+            ordering.then_with(|| {
+                let lhs = field(&items[*a], &sort.field);
+                let rhs = field(&items[*b], &sort.field);
+                let ordering = compare(lhs.as_ref(), rhs.as_ref());
+
+                match sort.direction {
+                    Direction::Asc => ordering,
+                    Direction::Desc => ordering.reverse(),
+                }
+            })
+        })
+    });
+
+    let total = matches.len() as u64;
+    let page = matches
+        .into_iter()
+        .skip(offset as usize)
+        .take(size.map_or(usize::max_value(), |size| size as usize))
+        .collect();
+
+    Ok((page, total))
+}
+
+/// Returns the value of the field at `path` of `item`.
+fn field(item: &Object, path: &Path) -> Option<Value> {
+    let (key, rest) = path.split_first()?;
+
+    if let Some(value) = item.attributes.get(key) {
+        return rest.iter()
+            .try_fold(value, |value, key| value.as_object()?.get(key))
+            .cloned();
+    }
+
+    if let Some(relationship) = item.relationships.get(key) {
+        if !rest.is_empty() && (rest.len() > 1 || &*rest[0] != "id") {
+            return None;
+        }
+
+        return Some(match relationship.data {
+            Data::Collection(ref data) => {
+                data.iter().map(|ident| Value::String(ident.id.clone())).collect()
+            }
+            Data::Member(ref data) => match **data {
+                Some(ref ident) => Value::String(ident.id.clone()),
+                None => Value::Null,
+            },
+        });
+    }
+
+    match &**key {
+        "id" if rest.is_empty() => Some(Value::String(item.id.clone())),
+        _ => None,
+    }
+}
+
+/// Returns true if `item` matches `filter`.
+fn eval(filter: &Filter, item: &Object) -> Result<bool, Error> {
+    match *filter {
+        Filter::And(ref filters) => {
+            for filter in filters {
+                if !eval(filter, item)? {
+                    return Ok(false);
+                }
+            }
+
+            Ok(true)
+        }
+        Filter::Or(ref filters) => {
+            for filter in filters {
+                if eval(filter, item)? {
+                    return Ok(true);
+                }
+            }
+
+            Ok(false)
+        }
+        Filter::Not(ref filter) => eval(filter, item).map(|value| !value),
+        Filter::In(ref path, ref expected) => {
+            let parameter = format!("filter[{}]", path);
+
+            for value in expected {
+                scalar(&parameter, value)?;
+            }
+
+            Ok(field(item, path).map_or(false, |value| {
+                expected.iter().any(|expected| equals(&value, expected))
+            }))
+        }
+        Filter::Compare(ref path, op, ref expected) => {
+            let parameter = format!("filter[{}]", path);
+
+            match (op, scalar(&parameter, expected)?) {
+                (Operator::Like, &Value::String(_)) => {}
+                (Operator::Like, _) => {
+                    let detail = "the value of a like filter must be a string";
+                    return Err(Error::invalid_parameter(&parameter, detail));
+                }
+                (Operator::Eq, _) | (Operator::Ne, _) => {}
+                (_, &Value::Null) => {
+                    let detail = format!("null cannot be compared with '{}'", op);
+                    return Err(Error::invalid_parameter(&parameter, &detail));
+                }
+                _ => {}
+            }
+
+            Ok(match field(item, path) {
+                Some(value) => matches(&value, op, expected),
+                None => op == Operator::Eq && expected.is_null(),
+            })
+        }
+    }
+}
+
+/// Returns true if `value` compares to `expected` with `op`.
+fn matches(value: &Value, op: Operator, expected: &Value) -> bool {
+    match (value, op, expected) {
+        (&Value::Array(ref items), _, _) => {
+            items.iter().any(|item| matches(item, op, expected))
+        }
+        (_, Operator::Eq, _) => equals(value, expected),
+        (&Value::Null, Operator::Ne, _) => false,
+        (_, Operator::Ne, _) => !equals(value, expected),
+        (&Value::String(ref value), Operator::Like, &Value::String(ref pattern)) => {
+            like(value, pattern)
+        }
+        (_, Operator::Like, _) => false,
+        (_, op, _) => match order(value, expected) {
+            Some(Ordering::Less) => op == Operator::Lt || op == Operator::Lte,
+            Some(Ordering::Equal) => op == Operator::Gte || op == Operator::Lte,
+            Some(Ordering::Greater) => op == Operator::Gt || op == Operator::Gte,
+            None => false,
+        },
+    }
+}
+
+/// Returns the ordering of `value` and the filter value `expected`, if they can be
+/// compared.
+fn order(value: &Value, expected: &Value) -> Option<Ordering> {
+    match (value, expected) {
+        (&Value::Bool(a), &Value::Bool(b)) => Some(a.cmp(&b)),
+        (&Value::Bool(a), &Value::String(ref b)) => b.parse().ok().map(|b| a.cmp(&b)),
+        (&Value::Number(_), &Value::Number(_)) => {
+            value.as_f64()?.partial_cmp(&expected.as_f64()?)
+        }
+        (&Value::Number(_), &Value::String(ref b)) => {
+            value.as_f64()?.partial_cmp(&b.parse::<f64>().ok()?)
+        }
+        (&Value::String(ref a), &Value::String(ref b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Returns true if `value` matches `pattern`, where `*` matches any sequence of
+/// characters.
+fn like(value: &str, pattern: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+
+    if !value.starts_with(first) {
+        return false;
+    }
+
+    let parts = parts.collect::<Vec<_>>();
+    let mut rest = &value[first.len()..];
+
+    match parts.split_last() {
+        Some((last, middle)) => {
+            for part in middle {
+                match rest.find(part) {
+                    Some(idx) => rest = &rest[idx + part.len()..],
+                    None => return false,
+                }
+            }
+
+            rest.len() >= last.len() && rest.ends_with(last)
+        }
+        None => rest.is_empty(),
+    }
+}
+
+fn scalar<'a>(parameter: &str, value: &'a Value) -> Result<&'a Value, Error> {
+    match *value {
+        Value::Array(_) | Value::Object(_) => {
+            let detail = "a filter value must be a string, number, boolean, or null";
+            Err(Error::invalid_parameter(parameter, detail))
+        }
+        ref value => Ok(value),
+    }
+}
+
+fn compare(lhs: Option<&Value>, rhs: Option<&Value>) -> Ordering {
+    fn rank(value: Option<&Value>) -> u8 {
+        match value {
+            None => 0,
+            Some(&Value::Null) => 1,
+            Some(&Value::Bool(_)) => 2,
+            Some(&Value::Number(_)) => 3,
+            Some(&Value::String(_)) => 4,
+            Some(&Value::Array(_)) => 5,
+            Some(&Value::Object(_)) => 6,
+        }
+    }
+
+    match (lhs, rhs) {
+        (Some(&Value::Bool(a)), Some(&Value::Bool(b))) => a.cmp(&b),
+        (Some(&Value::Number(ref a)), Some(&Value::Number(ref b))) => {
+            let a = a.as_f64().unwrap_or(0.0);
+            let b = b.as_f64().unwrap_or(0.0);
+
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        }
+        (Some(&Value::String(ref a)), Some(&Value::String(ref b))) => {
+            match (number(a), number(b)) {
+                (Some(x), Some(y)) => x.partial_cmp(&y)
+                    .unwrap_or(Ordering::Equal)
+                    .then_with(|| a.cmp(b)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => a.cmp(b),
+            }
+        }
+        _ => rank(lhs).cmp(&rank(rhs)),
+    }
+}
+
+/// Parses `value` as a number if it is the representation of a finite number.
+fn number(value: &str) -> Option<f64> {
+    value.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+fn equals(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (&Value::Array(ref items), _) => items.iter().any(|item| equals(item, expected)),
+        (_, &Value::Array(ref items)) => items.iter().any(|item| equals(value, item)),
+        (&Value::Bool(a), &Value::String(ref b)) => a.to_string() == *b,
+        (&Value::Null, &Value::String(ref b)) => b == "null",
+        (&Value::Number(ref a), &Value::String(ref b)) => {
+            a.to_string() == *b || b.parse::<f64>().ok() == a.as_f64()
+        }
+        _ => value == expected,
+    }
+}
@@ -1,24 +1,46 @@
 //! An API for working with well-known query parameters.
 
+pub mod sql;
+
 mod builder;
+mod cursor;
+mod filter;
+mod links;
+mod memory;
+mod offset;
 mod page;
+mod pagination;
+mod policy;
 mod sort;
+mod validate;
 
 use std::fmt::{self, Formatter};
 
 use percent_encoding::percent_decode;
 use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
-use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde::ser::{Serialize, SerializeMap, Serializer};
 use serde_qs;
 
+use doc::ErrorObject;
 use error::Error;
+use schema::Registry;
 use value::{Key, Map, Path, Set, Value};
 
 pub use self::builder::Builder;
+pub use self::cursor::Cursor;
+pub use self::filter::{Filter, Operator};
+pub use self::links::{cursor_links, page_links};
+pub use self::memory::{apply, apply_objects};
+pub use self::offset::Offset;
 pub use self::page::Page;
+pub use self::pagination::Pagination;
+pub use self::policy::{Overflow, QueryPolicy};
 pub use self::sort::{Direction, Sort};
 
-/// Represents well-known query parameters.
+/// The names of the well-known query parameters.
+const RESERVED: &[&str] = &["fields", "filter", "include", "page", "sort"];
+
+/// Represents well-known query parameters and any implementation-specific parameters.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Query {
     /// A map where each key is a type name and the value is set of field names
@@ -41,9 +63,13 @@ pub struct Query {
     /// A map where each key is a field path and the value is the value the client
     /// would like each item in the return document to have for the given field.
     ///
+    /// Use [`Filter::from_map`] to parse the filter parameters as a structured filter
+    /// expression.
+    ///
     /// For more information, check out the *[filter]* section of the JSON API
     /// specification.
     ///
+    /// [`Filter::from_map`]: ./enum.Filter.html#method.from_map
     /// [filtering]: http://jsonapi.org/format/#fetching-filtering
     pub filter: Map<Path, Value>,
 
@@ -57,13 +83,18 @@ pub struct Query {
     pub include: Set<Path>,
 
     /// Optional pagination parameters. To make life easier when this value is `None`,
-    /// the `Page` struct implements a sensible default.
+    /// the `Pagination` enum implements a sensible default.
+    ///
+    /// Page number based pagination is used unless the query contains the
+    /// `page[after]` or `page[before]` parameter of cursor based pagination, or the
+    /// `page[offset]` or `page[limit]` parameter of offset and limit based pagination.
+    /// Parameters of different pagination strategies cannot be combined.
     ///
     /// For more information, check out the *[pagination]* section of the JSON API
     /// specification.
     ///
     /// [pagination]: http://jsonapi.org/format/#fetching-pagination
-    pub page: Option<Page>,
+    pub page: Option<Pagination>,
 
     /// A set of sort instructions. Each element in the set contains the field name, and
     /// the sort direction (ascending or descending).
@@ -78,6 +109,20 @@ pub struct Query {
     /// [sorting]: http://jsonapi.org/format/#fetching-sorting
     pub sort: Set<Sort>,
 
+    /// Implementation-specific query parameters, keyed by the name of the parameter.
+    /// Each parameter that is not one of the parameters above is kept in this map.
+    ///
+    /// A parameter name is not converted to kebab-case like a member name and its
+    /// value is kept as is (i.e `locale=en` is the string `"en"` and `geo[lat]=0` is an
+    /// object). A parameter with the same name as a well-known query parameter is
+    /// ignored when the query is serialized.
+    ///
+    /// For more information, check out the *[query parameters]* section of the JSON API
+    /// specification.
+    ///
+    /// [query parameters]: http://jsonapi.org/format/#query-parameters
+    pub extra: Map<String, Value>,
+
     /// Private field for backwards compatibility.
     _ext: (),
 }
@@ -93,6 +138,65 @@ impl Query {
     pub fn builder() -> Builder {
         Default::default()
     }
+
+    /// Checks that each field and relationship path of the query exists in the schema
+    /// of the resource of type `kind` or a related resource. Returns an error object
+    /// with the status `400 Bad Request` and the name of the query parameter for each
+    /// violation.
+    ///
+    /// * Each field of a sparse fieldset must be an attribute or relationship of the
+    ///   type in the parameter (i.e `fields[articles]`).
+    /// * Each path in the `include` parameter must be a relationship path.
+    /// * Each field in the `sort` parameter must be the `id` or an attribute of the
+    ///   resource, or of a resource that is related through to-one relationships (i.e
+    ///   `author.name`).
+    ///
+    /// A type that is not in `registry` does not have any fields.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::query;
+    /// use json_api::schema::{Registry, Relation, Schema};
+    ///
+    /// let mut articles = Schema::new("articles".parse()?);
+    /// let mut registry = Registry::new();
+    ///
+    /// articles.attributes.insert("title".parse()?);
+    /// articles
+    ///     .relationships
+    ///     .insert("comments".parse()?, Relation::HasMany("comments".parse()?));
+    ///
+    /// registry.insert(articles);
+    /// registry.insert(Schema::new("comments".parse()?));
+    ///
+    /// let query = query::from_str("fields[articles]=title&include=comments")?;
+    /// assert!(query.validate("articles", &registry).is_empty());
+    ///
+    /// let query = query::from_str("fields[articles]=body&include=comments.author")?;
+    /// let errors = query.validate("articles", &registry);
+    /// let parameters = errors
+    ///     .iter()
+    ///     .filter_map(|error| error.source.as_ref()?.parameter.as_ref())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(parameters, vec!["fields[articles]", "include"]);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn validate(&self, kind: &str, registry: &Registry) -> Vec<ErrorObject> {
+        validate::query(self, kind, registry)
+    }
 }
 
 impl<'de> Deserialize<'de> for Query {
@@ -100,18 +204,6 @@ impl<'de> Deserialize<'de> for Query {
     where
         D: Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["fields", "filter", "include", "page", "sort"];
-
-        #[derive(Debug, Deserialize)]
-        #[serde(field_identifier, rename_all = "lowercase")]
-        enum Field {
-            Fields,
-            Filter,
-            Include,
-            Page,
-            Sort,
-        }
-
         struct QueryVisitor;
 
         impl<'de> Visitor<'de> for QueryVisitor {
@@ -127,15 +219,16 @@ impl<'de> Deserialize<'de> for Query {
             {
                 use serde::de::Error;
 
+                let mut extra = Map::new();
                 let mut fields = None;
                 let mut filter = None;
                 let mut include = None;
                 let mut page = None;
                 let mut sort = None;
 
-                while let Some(key) = access.next_key()? {
-                    match key {
-                        Field::Fields => {
+                while let Some(key) = access.next_key::<String>()? {
+                    match key.as_str() {
+                        "fields" => {
                             let data = access.next_value::<Map<_, String>>()?;
                             let mut map = Map::with_capacity(data.len());
 
@@ -146,24 +239,28 @@ impl<'de> Deserialize<'de> for Query {
 
                             fields = Some(map);
                         }
-                        Field::Filter => {
+                        "filter" => {
                             filter = Some(access.next_value()?);
                         }
-                        Field::Include => {
+                        "include" => {
                             let data = access.next_value::<String>()?;
                             include = Some(data.parse().map_err(Error::custom)?);
                         }
-                        Field::Page => {
+                        "page" => {
                             page = Some(access.next_value()?);
                         }
-                        Field::Sort => {
+                        "sort" => {
                             let data = access.next_value::<String>()?;
                             sort = Some(data.parse().map_err(Error::custom)?);
                         }
+                        _ => {
+                            extra.insert(key, access.next_value()?);
+                        }
                     }
                 }
 
                 Ok(Query {
+                    extra,
                     page,
                     fields: fields.unwrap_or_default(),
                     filter: filter.unwrap_or_default(),
@@ -174,7 +271,7 @@ impl<'de> Deserialize<'de> for Query {
             }
         }
 
-        deserializer.deserialize_struct("Query", FIELDS, QueryVisitor)
+        deserializer.deserialize_map(QueryVisitor)
     }
 }
 
@@ -183,7 +280,7 @@ impl Serialize for Query {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Query", 5)?;
+        let mut state = serializer.serialize_map(None)?;
 
         if !self.fields.is_empty() {
             let mut fields = Map::with_capacity(self.fields.len());
@@ -192,23 +289,29 @@ impl Serialize for Query {
                 fields.insert(key, value.to_string());
             }
 
-            state.serialize_field("fields", &fields)?;
+            state.serialize_entry("fields", &fields)?;
         }
 
         if !self.filter.is_empty() {
-            state.serialize_field("filter", &self.filter)?;
+            state.serialize_entry("filter", &self.filter)?;
         }
 
         if !self.include.is_empty() {
-            state.serialize_field("include", &self.include.to_string())?;
+            state.serialize_entry("include", &self.include.to_string())?;
         }
 
         if let Some(ref page) = self.page {
-            state.serialize_field("page", page)?;
+            state.serialize_entry("page", page)?;
         }
 
         if !self.sort.is_empty() {
-            state.serialize_field("sort", &self.sort.to_string())?;
+            state.serialize_entry("sort", &self.sort.to_string())?;
+        }
+
+        for (key, value) in &self.extra {
+            if !RESERVED.contains(&key.as_str()) {
+                state.serialize_entry(key, value)?;
+            }
         }
 
         state.end()
@@ -0,0 +1,53 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Offset and limit based pagination parameters.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Offset {
+    /// The number of items to skip before the first item of the page.
+    pub offset: u64,
+
+    /// Optionally specifies the maximum number of items to include in the page.
+    pub limit: Option<u64>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Offset {
+    /// Returns a new `Offset`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::query::Offset;
+    /// assert_eq!(Offset::new(0, None), Offset::default());
+    /// # }
+    /// ```
+    pub fn new(offset: u64, limit: Option<u64>) -> Self {
+        Offset {
+            offset,
+            limit,
+            _ext: (),
+        }
+    }
+}
+
+impl Serialize for Offset {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Offset", 2)?;
+
+        state.serialize_field("offset", &self.offset)?;
+
+        if let Some(ref value) = self.limit {
+            state.serialize_field("limit", value)?;
+        }
+
+        state.end()
+    }
+}
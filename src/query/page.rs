@@ -40,6 +40,26 @@ impl Page {
             _ext: (),
         }
     }
+
+    /// Returns the number of items to skip before the first item of the page. Returns
+    /// `None` if the page size is not specified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::query::Page;
+    ///
+    /// assert_eq!(Page::new(1, Some(10)).offset(), Some(0));
+    /// assert_eq!(Page::new(3, Some(10)).offset(), Some(20));
+    /// assert_eq!(Page::new(3, None).offset(), None);
+    /// # }
+    /// ```
+    pub fn offset(&self) -> Option<u64> {
+        self.size.map(|size| (self.number - 1).saturating_mul(size))
+    }
 }
 
 impl Default for Page {
@@ -0,0 +1,195 @@
+use std::fmt::{self, Formatter};
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use query::{Cursor, Offset, Page};
+
+/// The pagination parameters of a query. Each variant represents a different
+/// pagination strategy.
+///
+/// For more information, check out the *[pagination]* section of the JSON API
+/// specification.
+///
+/// [pagination]: http://jsonapi.org/format/#fetching-pagination
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Pagination {
+    /// Cursor based pagination (`page[after]`, `page[before]`, and `page[size]`).
+    Cursor(Cursor),
+
+    /// Offset and limit based pagination (`page[offset]` and `page[limit]`).
+    Offset(Offset),
+
+    /// Page number based pagination (`page[number]` and `page[size]`).
+    Page(Page),
+}
+
+impl Pagination {
+    /// Returns the number of items to skip before the first item of the page. Returns
+    /// `None` for cursor based pagination or if the page size of page number based
+    /// pagination is not specified.
+    ///
+    /// The returned value can be used as the `OFFSET` of a SQL query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::query::{Offset, Page, Pagination};
+    ///
+    /// assert_eq!(Pagination::from(Page::new(3, Some(10))).offset(), Some(20));
+    /// assert_eq!(Pagination::from(Offset::new(5, Some(10))).offset(), Some(5));
+    /// # }
+    /// ```
+    pub fn offset(&self) -> Option<u64> {
+        match *self {
+            Pagination::Cursor(_) => None,
+            Pagination::Offset(ref offset) => Some(offset.offset),
+            Pagination::Page(ref page) => page.offset(),
+        }
+    }
+
+    /// Returns the maximum number of items to include per page, if specified. This is
+    /// the value of `page[limit]` for offset and limit based pagination and the value
+    /// of `page[size]` otherwise.
+    ///
+    /// The returned value can be used as the `LIMIT` of a SQL query.
+    pub fn size(&self) -> Option<u64> {
+        match *self {
+            Pagination::Cursor(ref cursor) => cursor.size,
+            Pagination::Offset(ref offset) => offset.limit,
+            Pagination::Page(ref page) => page.size,
+        }
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Pagination::Page(Default::default())
+    }
+}
+
+impl From<Cursor> for Pagination {
+    fn from(cursor: Cursor) -> Self {
+        Pagination::Cursor(cursor)
+    }
+}
+
+impl From<Offset> for Pagination {
+    fn from(offset: Offset) -> Self {
+        Pagination::Offset(offset)
+    }
+}
+
+impl From<Page> for Pagination {
+    fn from(page: Page) -> Self {
+        Pagination::Page(page)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pagination {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::{MapAccess, Visitor};
+
+        const FIELDS: &[&str] = &["after", "before", "limit", "number", "offset", "size"];
+
+        #[derive(Debug, Deserialize)]
+        #[serde(field_identifier, rename_all = "lowercase")]
+        enum Field {
+            After,
+            Before,
+            Limit,
+            Number,
+            Offset,
+            Size,
+        }
+
+        struct PaginationVisitor;
+
+        impl<'de> Visitor<'de> for PaginationVisitor {
+            type Value = Pagination;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "an object containing json api pagination parameters")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                use serde::de::Error;
+
+                let mut after = None;
+                let mut before = None;
+                let mut limit = None;
+                let mut number = None;
+                let mut offset = None;
+                let mut size = None;
+
+                while let Some(key) = access.next_key()? {
+                    match key {
+                        Field::After => {
+                            after = access.next_value()?;
+                        }
+                        Field::Before => {
+                            before = access.next_value()?;
+                        }
+                        Field::Limit => {
+                            limit = access.next_value()?;
+                        }
+                        Field::Number => {
+                            number = access.next_value()?;
+                        }
+                        Field::Offset => {
+                            offset = access.next_value()?;
+                        }
+                        Field::Size => {
+                            size = access.next_value()?;
+                        }
+                    }
+                }
+
+                let is_cursor = after.is_some() || before.is_some();
+                let is_offset = offset.is_some() || limit.is_some();
+
+                if is_cursor && (is_offset || number.is_some()) {
+                    Err(A::Error::custom(
+                        "page[after] and page[before] cannot be used with other pagination \
+                         parameters except page[size]",
+                    ))
+                } else if is_offset && (number.is_some() || size.is_some()) {
+                    Err(A::Error::custom(
+                        "page[offset] and page[limit] cannot be used with page[number] or \
+                         page[size]",
+                    ))
+                } else if is_cursor {
+                    Ok(Cursor::new(after, before, size).into())
+                } else if is_offset {
+                    Ok(Offset::new(offset.unwrap_or(0), limit).into())
+                } else {
+                    Ok(Page::new(number.unwrap_or(1), size).into())
+                }
+            }
+        }
+
+        deserializer.deserialize_struct("Pagination", FIELDS, PaginationVisitor)
+    }
+}
+
+impl Serialize for Pagination {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            Pagination::Cursor(ref cursor) => cursor.serialize(serializer),
+            Pagination::Offset(ref offset) => offset.serialize(serializer),
+            Pagination::Page(ref page) => page.serialize(serializer),
+        }
+    }
+}
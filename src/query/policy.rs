@@ -0,0 +1,151 @@
+use error::Error;
+use query::{Pagination, Query, Sort};
+use value::{Key, Map, Set};
+
+/// Normalizes the queries of incoming requests with server-side defaults and limits.
+///
+/// A policy fills in the parameters that a client left out and enforces a maximum page
+/// size. It is meant to be applied to each query before it is used to fetch or render
+/// data, so every handler sees a query that is within the limits of the server.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, Overflow, Pagination, QueryPolicy};
+///
+/// let mut policy = QueryPolicy::new();
+///
+/// policy.default_size = Some(25);
+/// policy.max_size = Some(100);
+/// policy.default_sort = "-created-at".parse()?;
+/// policy.default_fields.insert("users".parse()?, "name".parse()?);
+///
+/// let mut query = query::from_str("page[size]=1000000")?;
+/// policy.apply(&mut query)?;
+///
+/// assert_eq!(query.page.as_ref().and_then(Pagination::size), Some(100));
+/// assert_eq!(query.sort.to_string(), "-created-at");
+/// assert_eq!(query.fields.get("users"), Some(&"name".parse()?));
+///
+/// policy.overflow = Overflow::Reject;
+///
+/// let mut query = query::from_str("page[size]=1000000")?;
+/// assert!(policy.apply(&mut query).is_err());
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QueryPolicy {
+    /// The sparse fieldset of each type that is used if the query does not contain a
+    /// fieldset for the type.
+    pub default_fields: Map<Key, Set>,
+
+    /// The page size that is used if the query does not specify one. If the query does
+    /// not contain any page parameters, page number based pagination is used. A default
+    /// that is larger than `max_size` is lowered to `max_size`.
+    pub default_size: Option<u64>,
+
+    /// The sort instructions that are used if the query does not contain any.
+    pub default_sort: Set<Sort>,
+
+    /// The maximum page size. This is also the page size of a query that does not
+    /// specify one if `default_size` is `None`.
+    pub max_size: Option<u64>,
+
+    /// What to do with a page size that is larger than `max_size`.
+    pub overflow: Overflow,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl QueryPolicy {
+    /// Returns a new policy without any defaults or limits.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Applies the defaults and limits of the policy to `query`.
+    ///
+    /// An error is returned if the page size that the query specifies is larger than
+    /// `max_size` and `overflow` is [`Overflow::Reject`]. The query is not modified if
+    /// an error is returned.
+    ///
+    /// [`Overflow::Reject`]: ./enum.Overflow.html#variant.Reject
+    pub fn apply(&self, query: &mut Query) -> Result<(), Error> {
+        let mut page = query.page.clone();
+        let default = match (self.default_size, self.max_size) {
+            (Some(value), Some(max)) => Some(value.min(max)),
+            (value, max) => value.or(max),
+        };
+
+        if page.is_none() && (self.default_size.is_some() || self.max_size.is_some()) {
+            page = Some(Default::default());
+        }
+
+        if let Some(ref mut page) = page {
+            let (parameter, size) = match *page {
+                Pagination::Cursor(ref mut cursor) => ("page[size]", &mut cursor.size),
+                Pagination::Offset(ref mut offset) => {
+                    ("page[limit]", &mut offset.limit)
+                }
+                Pagination::Page(ref mut page) => ("page[size]", &mut page.size),
+            };
+
+            match (*size, self.max_size) {
+                (Some(value), Some(max)) if value > max => match self.overflow {
+                    Overflow::Clamp => *size = Some(max),
+                    Overflow::Reject => {
+                        let detail = format!("the page size cannot exceed {}", max);
+                        return Err(Error::invalid_parameter(parameter, &detail));
+                    }
+                },
+                (None, _) => *size = default,
+                _ => {}
+            }
+        }
+
+        query.page = page;
+
+        if query.sort.is_empty() {
+            query.sort = self.default_sort.clone();
+        }
+
+        for (kind, fields) in &self.default_fields {
+            if !query.fields.contains_key(kind) {
+                query.fields.insert(kind.clone(), fields.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Determines what a [`QueryPolicy`] does with a page size that exceeds the maximum.
+///
+/// [`QueryPolicy`]: ./struct.QueryPolicy.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Overflow {
+    /// Use the maximum page size instead.
+    Clamp,
+
+    /// Return an error that maps to an error object with the status
+    /// `400 Bad Request`.
+    Reject,
+}
+
+impl Default for Overflow {
+    fn default() -> Self {
+        Overflow::Clamp
+    }
+}
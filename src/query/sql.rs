@@ -0,0 +1,420 @@
+//! Translate the parameters of a query into parameterized SQL.
+//!
+//! The field paths of a query are never written to SQL. Each path is looked up in a
+//! [`Columns`] mapping that lists the columns a client is allowed to filter and sort
+//! by. A parameter that uses a path that is not in the mapping is rejected with an
+//! `InvalidParameter` error. Every value from the query string is bound as a
+//! parameter with a `?` placeholder.
+//!
+//! The generated SQL uses standard syntax that is supported by SQLite, PostgreSQL
+//! (once the placeholders are numbered), and MySQL.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate json_api;
+//! #
+//! # use json_api::Error;
+//! #
+//! # fn example() -> Result<(), Error> {
+//! use json_api::query::{self, sql};
+//! use json_api::value::Value;
+//!
+//! let mut columns = sql::Columns::new();
+//!
+//! columns.insert("title".parse()?, "title")?;
+//! columns.insert("author.name".parse()?, "users.name")?;
+//!
+//! let query = query::from_str(concat!(
+//!     "filter[author.name]=Dan&",
+//!     "filter[title][like]=Rust*&",
+//!     "sort=-title&",
+//!     "page[number]=2&",
+//!     "page[size]=10",
+//! ))?;
+//!
+//! let clauses = sql::from_query(&query, &columns)?;
+//! let fragment = clauses.to_fragment();
+//!
+//! assert_eq!(fragment.sql, concat!(
+//!     r#"WHERE ("users"."name" = ? AND "title" LIKE ? ESCAPE '\') "#,
+//!     r#"ORDER BY "title" DESC "#,
+//!     r#"LIMIT ? OFFSET ?"#,
+//! ));
+//!
+//! assert_eq!(fragment.params, vec![
+//!     Value::from("Dan"),
+//!     Value::from("Rust%"),
+//!     Value::from(10),
+//!     Value::from(10),
+//! ]);
+//!
+//! let query = query::from_str("sort=body")?;
+//! assert!(sql::from_query(&query, &columns).is_err());
+//! #
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+//!
+//! [`Columns`]: ./struct.Columns.html
+
+use error::Error;
+use query::{Direction, Filter, Operator, Pagination, Query, Sort};
+use value::{Map, Path, Set, Value};
+
+/// A mapping from the field paths of a resource to the columns of a table.
+///
+/// The mapping is also an allowlist. A query can only filter or sort by a field path
+/// that has a column.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Columns {
+    map: Map<Path, String>,
+}
+
+impl Columns {
+    /// Returns a new, empty mapping.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Maps `path` to `column`. A column can be qualified with the name of a table
+    /// (i.e `users.name`).
+    ///
+    /// An error is returned if the name of the column or table is not a valid SQL
+    /// identifier. A valid identifier starts with an ASCII letter or `_`, followed by
+    /// any number of ASCII letters, digits, or `_`.
+    pub fn insert(&mut self, path: Path, column: &str) -> Result<(), Error> {
+        let mut quoted = String::with_capacity(column.len() + 4);
+
+        for (idx, part) in column.split('.').enumerate() {
+            if !is_identifier(part) {
+                bail!("invalid column name '{}'", column);
+            }
+
+            if idx > 0 {
+                quoted.push('.');
+            }
+
+            quoted.push('"');
+            quoted.push_str(part);
+            quoted.push('"');
+        }
+
+        self.map.insert(path, quoted);
+        Ok(())
+    }
+
+    /// Returns the quoted column that `path` is mapped to.
+    pub fn get(&self, path: &Path) -> Option<&str> {
+        self.map.get(path).map(String::as_str)
+    }
+
+    /// Returns `true` if `path` is mapped to a column.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.map.contains_key(path)
+    }
+
+    fn column(&self, parameter: &str, path: &Path) -> Result<&str, Error> {
+        match self.get(path) {
+            Some(column) => Ok(column),
+            None => Err(Error::invalid_parameter(
+                parameter,
+                &format!("cannot filter or sort by '{}'", path),
+            )),
+        }
+    }
+}
+
+/// A fragment of SQL and the values that are bound to its placeholders.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Fragment {
+    /// The SQL of the fragment. Each value is represented by a `?` placeholder.
+    pub sql: String,
+
+    /// The values of the placeholders in `sql`, in order.
+    pub params: Vec<Value>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Fragment {
+    /// Returns a new, empty fragment.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns `true` if the fragment does not contain any SQL.
+    pub fn is_empty(&self) -> bool {
+        self.sql.is_empty()
+    }
+
+    fn bind(&mut self, value: Value) {
+        self.sql.push('?');
+        self.params.push(value);
+    }
+}
+
+/// The clauses of a `SELECT` statement that are translated from a query.
+///
+/// A clause is empty if the query does not contain the corresponding parameter.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Clauses {
+    /// The `WHERE` clause, translated from the filter parameters.
+    pub filter: Fragment,
+
+    /// The `ORDER BY` clause, translated from the sort parameter.
+    pub order: Fragment,
+
+    /// The `LIMIT` and `OFFSET` clause, translated from the page parameters.
+    pub page: Fragment,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Clauses {
+    /// Returns a single fragment that contains each clause that is not empty,
+    /// separated by a space.
+    pub fn to_fragment(&self) -> Fragment {
+        let clauses = [&self.filter, &self.order, &self.page];
+
+        clauses.iter().filter(|clause| !clause.is_empty()).fold(
+            Fragment::new(),
+            |mut fragment, clause| {
+                if !fragment.is_empty() {
+                    fragment.sql.push(' ');
+                }
+
+                fragment.sql.push_str(&clause.sql);
+                fragment.params.extend(clause.params.iter().cloned());
+                fragment
+            },
+        )
+    }
+}
+
+/// Translates the filter, sort, and page parameters of `query` into SQL clauses.
+///
+/// The filter parameters are parsed with [`Filter::from_map`]. A `like` pattern is
+/// translated to a `LIKE` expression where `*` matches any sequence of characters.
+/// An error is returned if a parameter uses a field path that is not in `columns`,
+/// or if the query uses cursor based pagination.
+///
+/// [`Filter::from_map`]: ../struct.Filter.html#method.from_map
+pub fn from_query(query: &Query, columns: &Columns) -> Result<Clauses, Error> {
+    let mut clauses = Clauses::default();
+
+    if !query.filter.is_empty() {
+        let filter = Filter::from_map(&query.filter)
+            .map_err(|e| Error::invalid_parameter("filter", &e.to_string()))?;
+
+        clauses.filter.sql.push_str("WHERE ");
+        filter_to_sql(&filter, columns, &mut clauses.filter)?;
+    }
+
+    if !query.sort.is_empty() {
+        clauses.order = order_to_sql(&query.sort, columns)?;
+    }
+
+    if let Some(ref page) = query.page {
+        clauses.page = page_to_sql(page)?;
+    }
+
+    Ok(clauses)
+}
+
+fn filter_to_sql(
+    filter: &Filter,
+    columns: &Columns,
+    dest: &mut Fragment,
+) -> Result<(), Error> {
+    match *filter {
+        Filter::And(ref filters) => group(filters, " AND ", "1 = 1", columns, dest)?,
+        Filter::Or(ref filters) => group(filters, " OR ", "1 = 0", columns, dest)?,
+        Filter::Not(ref filter) => {
+            dest.sql.push_str("NOT (");
+            filter_to_sql(filter, columns, dest)?;
+            dest.sql.push(')');
+        }
+        Filter::In(ref path, ref values) => {
+            let parameter = format!("filter[{}]", path);
+            let column = columns.column(&parameter, path)?;
+
+            if values.is_empty() {
+                dest.sql.push_str("1 = 0");
+                return Ok(());
+            }
+
+            dest.sql.push_str(column);
+            dest.sql.push_str(" IN (");
+
+            for (idx, value) in values.iter().enumerate() {
+                if idx > 0 {
+                    dest.sql.push_str(", ");
+                }
+
+                dest.bind(scalar(&parameter, value)?);
+            }
+
+            dest.sql.push(')');
+        }
+        Filter::Compare(ref path, op, ref value) => {
+            let parameter = format!("filter[{}]", path);
+            let column = columns.column(&parameter, path)?;
+
+            dest.sql.push_str(column);
+
+            match (op, scalar(&parameter, value)?) {
+                (Operator::Eq, Value::Null) => dest.sql.push_str(" IS NULL"),
+                (Operator::Ne, Value::Null) => dest.sql.push_str(" IS NOT NULL"),
+                (Operator::Like, Value::String(pattern)) => {
+                    dest.sql.push_str(" LIKE ");
+                    dest.bind(Value::String(like(&pattern)));
+                    dest.sql.push_str(" ESCAPE '\\'");
+                }
+                (Operator::Like, _) => {
+                    let detail = "the value of a like filter must be a string";
+                    return Err(Error::invalid_parameter(&parameter, detail));
+                }
+                (_, Value::Null) => {
+                    let detail = format!("null cannot be compared with '{}'", op);
+                    return Err(Error::invalid_parameter(&parameter, &detail));
+                }
+                (op, value) => {
+                    dest.sql.push_str(match op {
+                        Operator::Gt => " > ",
+                        Operator::Gte => " >= ",
+                        Operator::Lt => " < ",
+                        Operator::Lte => " <= ",
+                        Operator::Ne => " <> ",
+                        Operator::Eq | Operator::Like => " = ",
+                    });
+
+                    dest.bind(value);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn group(
+    filters: &[Filter],
+    separator: &str,
+    empty: &str,
+    columns: &Columns,
+    dest: &mut Fragment,
+) -> Result<(), Error> {
+    if filters.is_empty() {
+        dest.sql.push_str(empty);
+        return Ok(());
+    }
+
+    dest.sql.push('(');
+
+    for (idx, filter) in filters.iter().enumerate() {
+        if idx > 0 {
+            dest.sql.push_str(separator);
+        }
+
+        filter_to_sql(filter, columns, dest)?;
+    }
+
+    dest.sql.push(')');
+    Ok(())
+}
+
+fn order_to_sql(sort: &Set<Sort>, columns: &Columns) -> Result<Fragment, Error> {
+    let mut fragment = Fragment::new();
+
+    fragment.sql.push_str("ORDER BY ");
+
+    for (idx, item) in sort.iter().enumerate() {
+        if idx > 0 {
+            fragment.sql.push_str(", ");
+        }
+
+        fragment.sql.push_str(columns.column("sort", &item.field)?);
+        fragment.sql.push_str(match item.direction {
+            Direction::Asc => " ASC",
+            Direction::Desc => " DESC",
+        });
+    }
+
+    Ok(fragment)
+}
+
+fn page_to_sql(page: &Pagination) -> Result<Fragment, Error> {
+    let mut fragment = Fragment::new();
+
+    if let Pagination::Cursor(_) = *page {
+        let detail = "cursor based pagination cannot be translated to SQL";
+        return Err(Error::invalid_parameter("page", detail));
+    }
+
+    let offset = page.offset().unwrap_or(0);
+
+    // OFFSET without LIMIT is not supported by every database. The largest signed
+    // 64-bit integer is used as the limit of a page without a size instead.
+    let limit = match page.size() {
+        Some(size) => Value::from(size),
+        None if offset > 0 => Value::from(i64::max_value()),
+        None => return Ok(fragment),
+    };
+
+    fragment.sql.push_str("LIMIT ");
+    fragment.bind(limit);
+    fragment.sql.push_str(" OFFSET ");
+    fragment.bind(Value::from(offset));
+
+    Ok(fragment)
+}
+
+fn is_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+
+    match chars.next() {
+        Some('a'...'z') | Some('A'...'Z') | Some('_') => {}
+        _ => return false,
+    }
+
+    chars.all(|c| match c {
+        'a'...'z' | 'A'...'Z' | '0'...'9' | '_' => true,
+        _ => false,
+    })
+}
+
+/// Translates a `like` filter pattern to a SQL `LIKE` pattern that uses `\` as the
+/// escape character.
+fn like(pattern: &str) -> String {
+    let mut dest = String::with_capacity(pattern.len());
+
+    for c in pattern.chars() {
+        match c {
+            '*' => dest.push('%'),
+            '%' | '_' | '\\' => {
+                dest.push('\\');
+                dest.push(c);
+            }
+            _ => dest.push(c),
+        }
+    }
+
+    dest
+}
+
+fn scalar(parameter: &str, value: &Value) -> Result<Value, Error> {
+    match *value {
+        Value::Array(_) | Value::Object(_) => {
+            let detail = "a filter value must be a string, number, boolean, or null";
+            Err(Error::invalid_parameter(parameter, detail))
+        }
+        ref value => Ok(value.clone()),
+    }
+}
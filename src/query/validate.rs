@@ -0,0 +1,103 @@
+use doc::{ErrorObject, ErrorSource};
+use http::StatusCode;
+use query::Query;
+use schema::{Registry, Schema};
+use value::{Key, Path};
+
+pub fn query(query: &Query, kind: &str, registry: &Registry) -> Vec<ErrorObject> {
+    let mut errors = Vec::new();
+
+    for (kind, names) in &query.fields {
+        let parameter = format!("fields[{}]", kind);
+        let schema = match registry.get(kind) {
+            Some(schema) => schema,
+            None => {
+                let detail = format!(r#"There is no resource of type "{}"."#, kind);
+
+                errors.push(error(&parameter, &detail));
+                continue;
+            }
+        };
+
+        for name in names {
+            if !schema.has_field(name) {
+                let detail = format!(
+                    r#"The resource of type "{}" does not have a field named "{}"."#,
+                    kind, name
+                );
+
+                errors.push(error(&parameter, &detail));
+            }
+        }
+    }
+
+    for path in &query.include {
+        if let Err(prefix) = include(registry, kind, path) {
+            let detail = format!(
+                r#""{}" is not a relationship path of the resource of type "{}"."#,
+                prefix, kind
+            );
+
+            errors.push(error("include", &detail));
+        }
+    }
+
+    for item in &query.sort {
+        if !sort(registry.get(kind), registry, &item.field) {
+            let detail = format!(
+                r#"The resource of type "{}" cannot be sorted by "{}"."#,
+                kind, item.field
+            );
+
+            errors.push(error("sort", &detail));
+        }
+    }
+
+    errors
+}
+
+/// Returns the shortest prefix of `path` that is not a relationship path.
+fn include(registry: &Registry, kind: &str, path: &Path) -> Result<(), Path> {
+    let mut schema = registry.get(kind);
+
+    for (idx, key) in path.iter().enumerate() {
+        let relation = schema.and_then(|schema| schema.relationships.get(key));
+
+        match relation {
+            Some(relation) => schema = registry.get(relation.kind()),
+            None => return Err(path.iter().take(idx + 1).cloned().collect()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `path` is the id or an attribute of the resource, or a sortable
+/// field of a resource that is related through to-one relationships.
+fn sort(schema: Option<&Schema>, registry: &Registry, path: &[Key]) -> bool {
+    let schema = match schema {
+        Some(schema) => schema,
+        None => return false,
+    };
+
+    match path.split_first() {
+        Some((key, [])) => &**key == "id" || schema.attributes.contains(key),
+        Some((key, rest)) => match schema.relationships.get(key) {
+            Some(relation) if !relation.is_many() => {
+                sort(registry.get(relation.kind()), registry, rest)
+            }
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+fn error(parameter: &str, detail: &str) -> ErrorObject {
+    let mut error = ErrorObject::new(Some(StatusCode::BAD_REQUEST));
+
+    error.detail = Some(detail.to_owned());
+    error.source = Some(ErrorSource::new(Some(parameter.to_owned()), None));
+    error.title = Some("Invalid Query Parameter".to_owned());
+
+    error
+}
@@ -3,14 +3,17 @@ use std::mem;
 use doc::{Data, Document, Identifier, Object};
 use error::Error;
 use query::Query;
+use schema::Schema;
 use value::Set;
 use value::fields::Key;
-use view::{Context, Render};
+use view::{Context, Render, RenderPolicy};
 
 /// A trait indicating that the given type can be represented as a resource.
 ///
 /// Implementing this trait manually is not recommended. The [`resource!`] macro provides
-/// a friendly DSL that implements trait with some additional functionality.
+/// a friendly DSL that implements trait with some additional functionality. The
+/// [json-api-derive] crate provides `#[derive(Resource)]`, which can also be used with
+/// generic structs.
 ///
 /// # Example
 ///
@@ -29,6 +32,7 @@ use view::{Context, Render};
 /// ```
 ///
 /// [`resource!`]: ./macro.resource.html
+/// [json-api-derive]: https://docs.rs/json-api-derive/0.4
 pub trait Resource {
     /// Returns a key containing the type of resource.
     ///
@@ -94,6 +98,89 @@ pub trait Resource {
     ///
     /// [`json_api::to_doc`]: ./fn.to_doc.html
     fn to_object(&self, ctx: &mut Context) -> Result<Object, Error>;
+
+    /// Returns a description of the attributes, relationships, links, and meta of the
+    /// resource.
+    ///
+    /// The [`resource!`] macro implements this method. The default implementation
+    /// returns a schema that only contains the type of the resource.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # struct Post {
+    /// #     id: u64,
+    /// #     title: String,
+    /// #     author: Option<User>,
+    /// # }
+    /// #
+    /// # resource!(Post, |&self| {
+    /// #     kind "posts";
+    /// #     id self.id;
+    /// #     attrs title;
+    /// #     has_one author;
+    /// #     link "self", format!("/posts/{}", self.id);
+    /// # });
+    /// #
+    /// # struct User(u64);
+    /// #
+    /// # resource!(User, |&self| {
+    /// #     kind "users";
+    /// #     id self.0;
+    /// # });
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::Resource;
+    /// use json_api::schema::Relation;
+    ///
+    /// let schema = Post::schema()?;
+    ///
+    /// assert!(schema.attributes.contains("title"));
+    /// assert!(schema.links.contains("self"));
+    /// assert_eq!(
+    ///     schema.relationships.get("author"),
+    ///     Some(&Relation::HasOne("users".parse()?))
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`resource!`]: ./macro.resource.html
+    fn schema() -> Result<Schema, Error> {
+        Ok(Schema::new(Self::kind()))
+    }
+}
+
+impl<'a, T: Resource> Resource for &'a T {
+    fn kind() -> Key {
+        T::kind()
+    }
+
+    fn id(&self) -> String {
+        (*self).id()
+    }
+
+    fn to_ident(&self, ctx: &mut Context) -> Result<Identifier, Error> {
+        (*self).to_ident(ctx)
+    }
+
+    fn to_object(&self, ctx: &mut Context) -> Result<Object, Error> {
+        (*self).to_object(ctx)
+    }
+
+    fn schema() -> Result<Schema, Error> {
+        T::schema()
+    }
 }
 
 impl<'a, T: Resource> Render<Identifier> for &'a T {
@@ -119,9 +206,17 @@ impl<'a, T: Resource> Render<Identifier> for &'a [T] {
 
 impl<'a, T: Resource> Render<Object> for &'a T {
     fn render(self, query: Option<&Query>) -> Result<Document<Object>, Error> {
+        self.render_with(query, &Default::default())
+    }
+
+    fn render_with(
+        self,
+        query: Option<&Query>,
+        policy: &RenderPolicy,
+    ) -> Result<Document<Object>, Error> {
         let mut incl = Set::new();
         let (data, links, meta) = {
-            let mut ctx = Context::new(T::kind(), query, &mut incl);
+            let mut ctx = Context::with_policy(T::kind(), query, &mut incl, policy)?;
             let mut obj = self.to_object(&mut ctx)?;
             let links = mem::replace(&mut obj.links, Default::default());
             let meta = mem::replace(&mut obj.meta, Default::default());
@@ -141,11 +236,19 @@ impl<'a, T: Resource> Render<Object> for &'a T {
 
 impl<'a, T: Resource> Render<Object> for &'a [T] {
     fn render(self, query: Option<&Query>) -> Result<Document<Object>, Error> {
+        self.render_with(query, &Default::default())
+    }
+
+    fn render_with(
+        self,
+        query: Option<&Query>,
+        policy: &RenderPolicy,
+    ) -> Result<Document<Object>, Error> {
         let mut incl = Set::new();
         let mut data = Vec::with_capacity(self.len());
 
         {
-            let mut ctx = Context::new(T::kind(), query, &mut incl);
+            let mut ctx = Context::with_policy(T::kind(), query, &mut incl, policy)?;
 
             for item in self {
                 data.push(item.to_object(&mut ctx)?);
@@ -321,7 +424,7 @@ macro_rules! resource {
 
             fn to_ident(
                 &$this,
-                _: &mut $crate::view::Context,
+                _ctx: &mut $crate::view::Context,
             ) -> Result<$crate::doc::Identifier, $crate::Error> {
                 let mut ident = {
                     let kind = <$target as $crate::Resource>::kind();
@@ -332,7 +435,7 @@ macro_rules! resource {
 
                 {
                     let _meta = &mut ident.meta;
-                    expand_resource_impl!(@meta $this, _meta, {
+                    expand_resource_impl!(@meta $this, _meta, _ctx, {
                         $($rest)*
                     });
                 }
@@ -374,14 +477,14 @@ macro_rules! resource {
 
                 {
                     let _links = &mut obj.links;
-                    expand_resource_impl!(@links $this, _links, {
+                    expand_resource_impl!(@links $this, _links, ctx, {
                         $($rest)*
                     });
                 }
 
                 {
                     let _meta = &mut obj.meta;
-                    expand_resource_impl!(@meta $this, _meta, {
+                    expand_resource_impl!(@meta $this, _meta, ctx, {
                         $($rest)*
                     });
                 }
@@ -395,6 +498,30 @@ macro_rules! resource {
 
                 Ok(obj)
             }
+
+            fn schema() -> Result<$crate::schema::Schema, $crate::Error> {
+                #[allow(dead_code)]
+                fn kind_of<S, T>(_: fn(&S) -> Option<&T>) -> $crate::value::Key
+                where
+                    T: $crate::Resource,
+                {
+                    T::kind()
+                }
+
+                let mut schema = {
+                    let kind = <$target as $crate::Resource>::kind();
+                    $crate::schema::Schema::new(kind)
+                };
+
+                {
+                    let _schema = &mut schema;
+                    expand_resource_impl!(@schema $this, $target, _schema, {
+                        $($rest)*
+                    });
+                }
+
+                Ok(schema)
+            }
         }
     };
 }
@@ -406,8 +533,8 @@ macro_rules! expand_resource_impl {
         attr $key:expr, $value:block
         $($rest:tt)*
     }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
+        if $ctx.field(&$key.parse::<$crate::value::Key>()?) {
+            let key = $ctx.key($key)?;
             let value = $crate::to_value($value)?;
 
             $attrs.insert(key, value);
@@ -436,9 +563,11 @@ macro_rules! expand_resource_impl {
         has_many $key:expr, { $($body:tt)* }
         $($rest:tt)*
     }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
-            expand_resource_impl!(@has_many $this, $related, key, $ctx, {
+        let path = $key.parse::<$crate::value::Key>()?;
+
+        if $ctx.field(&path) {
+            let key = $ctx.key($key)?;
+            expand_resource_impl!(@has_many $this, $related, key, path, $ctx, {
                 $($body)*
             });
         }
@@ -452,9 +581,11 @@ macro_rules! expand_resource_impl {
         has_one $key:expr, { $($body:tt)* }
         $($rest:tt)*
     }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
-            expand_resource_impl!(@has_one $this, $related, key, $ctx, {
+        let path = $key.parse::<$crate::value::Key>()?;
+
+        if $ctx.field(&path) {
+            let key = $ctx.key($key)?;
+            expand_resource_impl!(@has_one $this, $related, key, path, $ctx, {
                 $($body)*
             });
         }
@@ -484,12 +615,12 @@ macro_rules! expand_resource_impl {
         });
     };
 
-    (@has_many $this:ident, $related:ident, $key:ident, $ctx:ident, {
+    (@has_many $this:ident, $related:ident, $key:ident, $path:ident, $ctx:ident, {
         data $value:block
         $($rest:tt)*
     }) => {
         let mut rel = $crate::doc::Relationship::new({
-            let mut ctx = $ctx.fork(iter_kind(&$value), &$key);
+            let mut ctx = $ctx.fork(iter_kind(&$value), &$path);
             let mut data = match $value.size_hint() {
                 (_, Some(size)) => Vec::with_capacity(size),
                 _ => Vec::new(),
@@ -500,7 +631,7 @@ macro_rules! expand_resource_impl {
                     let object = $crate::Resource::to_object(item, &mut ctx)?;
                     let ident = $crate::doc::Identifier::from(&object);
 
-                    ctx.include(object);
+                    ctx.try_include(object)?;
                     data.push(ident);
                 }
             } else {
@@ -514,14 +645,14 @@ macro_rules! expand_resource_impl {
 
         {
             let links = &mut rel.links;
-            expand_resource_impl!(@links $this, links, {
+            expand_resource_impl!(@links $this, links, $ctx, {
                 $($rest)*
             });
         }
 
         {
             let _meta = &mut rel.meta;
-            expand_resource_impl!(@meta $this, _meta, {
+            expand_resource_impl!(@meta $this, _meta, $ctx, {
                 $($rest)*
             });
         }
@@ -529,7 +660,7 @@ macro_rules! expand_resource_impl {
         $related.insert($key, rel);
     };
 
-    (@has_one $this:ident, $related:ident, $key:ident, $ctx:ident, {
+    (@has_one $this:ident, $related:ident, $key:ident, $path:ident, $ctx:ident, {
         data $value:block
         $($rest:tt)*
     }) => {
@@ -537,13 +668,13 @@ macro_rules! expand_resource_impl {
             let mut data = None;
 
             if let Some(item) = $value {
-                let mut ctx = $ctx.fork(item_kind(item), &$key);
+                let mut ctx = $ctx.fork(item_kind(item), &$path);
 
                 data = Some($crate::Resource::to_ident(item, &mut ctx)?);
 
                 if ctx.included() {
                     let object = $crate::Resource::to_object(item, &mut ctx)?;
-                    ctx.include(object);
+                    ctx.try_include(object)?;
                 }
             }
 
@@ -552,14 +683,14 @@ macro_rules! expand_resource_impl {
 
         {
             let _links = &mut rel.links;
-            expand_resource_impl!(@links $this, _links, {
+            expand_resource_impl!(@links $this, _links, $ctx, {
                 $($rest)*
             });
         }
 
         {
             let _meta = &mut rel.meta;
-            expand_resource_impl!(@meta $this, _meta, {
+            expand_resource_impl!(@meta $this, _meta, $ctx, {
                 $($rest)*
             });
         }
@@ -567,20 +698,20 @@ macro_rules! expand_resource_impl {
         $related.insert($key, rel);
     };
 
-    (@links $this:ident, $links:ident, {
+    (@links $this:ident, $links:ident, $ctx:ident, {
         link $key:expr, { $($body:tt)* }
         $($rest:tt)*
     }) => {
         {
-            let key = $key.parse::<$crate::value::Key>()?;
-            let link = expand_resource_impl!(@link $this, {
+            let key = $ctx.key($key)?;
+            let link = expand_resource_impl!(@link $this, $ctx, {
                 $($body)*
             });
 
             $links.insert(key, link);
         }
 
-        expand_resource_impl!(@links $this, $links, {
+        expand_resource_impl!(@links $this, $links, $ctx, {
             $($rest)*
         });
     };
@@ -595,12 +726,12 @@ macro_rules! expand_resource_impl {
         });
     };
 
-    (@link $this:ident, { href $value:block $($rest:tt)* }) => {{
+    (@link $this:ident, $ctx:ident, { href $value:block $($rest:tt)* }) => {{
         let mut link = $value.parse::<$crate::doc::Link>()?;
 
         {
             let _meta = &link.meta;
-            expand_resource_impl!(@meta $this, _meta, {
+            expand_resource_impl!(@meta $this, _meta, $ctx, {
                 $($rest)*
             });
         }
@@ -608,22 +739,187 @@ macro_rules! expand_resource_impl {
         link
     }};
 
-    (@meta $this:ident, $meta:ident, {
+    (@meta $this:ident, $meta:ident, $ctx:ident, {
         meta $key:expr, $value:block
         $($rest:tt)*
     }) => {
         {
-            let key = $key.parse::<$crate::value::Key>()?;
+            let key = $ctx.key($key)?;
             let value = $crate::to_value($value)?;
 
             $meta.insert(key, value);
         }
 
-        expand_resource_impl!(@meta $this, $meta, {
+        expand_resource_impl!(@meta $this, $meta, $ctx, {
+            $($rest)*
+        });
+    };
+
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        attr $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        $schema.attributes.insert($key.parse::<$crate::value::Key>()?);
+
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            $($rest)*
+        });
+    };
+
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        attr $field:ident;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            attr stringify!($field), {}
+            $($rest)*
+        });
+    };
+
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        attrs $($field:ident),+;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            $(attr $field;)+
+            $($rest)*
+        });
+    };
+
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        has_many $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        {
+            let key = $key.parse::<$crate::value::Key>()?;
+            let kind = expand_resource_impl!(@schema_many $this, $target, {
+                $($body)*
+            });
+
+            $schema
+                .relationships
+                .insert(key, $crate::schema::Relation::HasMany(kind));
+        }
+
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            $($rest)*
+        });
+    };
+
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        has_one $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        {
+            let key = $key.parse::<$crate::value::Key>()?;
+            let kind = expand_resource_impl!(@schema_one $this, $target, {
+                $($body)*
+            });
+
+            $schema
+                .relationships
+                .insert(key, $crate::schema::Relation::HasOne(kind));
+        }
+
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            $($rest)*
+        });
+    };
+
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        has_many $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            $(has_many stringify!($field), { data $this.$field.iter(); })*
             $($rest)*
         });
     };
 
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        has_one $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            $(has_one stringify!($field), { data $this.$field.as_ref(); })*
+            $($rest)*
+        });
+    };
+
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        link $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        $schema.links.insert($key.parse::<$crate::value::Key>()?);
+
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            $($rest)*
+        });
+    };
+
+    (@schema $this:ident, $target:ident, $schema:ident, {
+        meta $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        $schema.meta.insert($key.parse::<$crate::value::Key>()?);
+
+        expand_resource_impl!(@schema $this, $target, $schema, {
+            $($rest)*
+        });
+    };
+
+    // The data of a relationship is only used to infer the type of the related
+    // resource. It is wrapped in a method of a type that derefs to the target, so
+    // `self` can be used without an instance.
+    (@schema_many $this:ident, $target:ident, {
+        data $value:block
+        $($rest:tt)*
+    }) => {{
+        #[allow(dead_code)]
+        struct Probe($target);
+
+        impl ::std::ops::Deref for Probe {
+            type Target = $target;
+
+            fn deref(&self) -> &$target {
+                &self.0
+            }
+        }
+
+        impl Probe {
+            fn data(&$this) -> Option<&impl $crate::Resource> {
+                let mut iter = $value;
+                iter.next()
+            }
+        }
+
+        kind_of(Probe::data)
+    }};
+
+    (@schema_one $this:ident, $target:ident, {
+        data $value:block
+        $($rest:tt)*
+    }) => {{
+        #[allow(dead_code)]
+        struct Probe($target);
+
+        impl ::std::ops::Deref for Probe {
+            type Target = $target;
+
+            fn deref(&self) -> &$target {
+                &self.0
+            }
+        }
+
+        impl Probe {
+            fn data(&$this) -> Option<&impl $crate::Resource> {
+                $value
+            }
+        }
+
+        kind_of(Probe::data)
+    }};
+
     // Ignore has_many specific syntax in other scopes.
     (@$scope:tt $($args:ident),+, {
         has_many $key:expr, { $($body:tt)* }
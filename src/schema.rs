@@ -0,0 +1,165 @@
+//! Describe the fields of each type of resource at runtime.
+//!
+//! A [`Schema`] lists the attributes, relationships, links, and meta of a type of
+//! resource. The schema of a type that implements [`Resource`] with the [`resource!`]
+//! macro is returned by [`Resource::schema`]. A [`Registry`] holds the schema of each
+//! type that an API exposes. It can be used to check that the fields and relationship
+//! paths of a query exist with [`Query::validate`].
+//!
+//! [`Query::validate`]: ../query/struct.Query.html#method.validate
+//! [`Registry`]: ./struct.Registry.html
+//! [`Resource`]: ../trait.Resource.html
+//! [`Resource::schema`]: ../trait.Resource.html#method.schema
+//! [`Schema`]: ./struct.Schema.html
+//! [`resource!`]: ../macro.resource.html
+
+use error::Error;
+use resource::Resource;
+use value::collections::map::Iter;
+use value::{Key, Map, Set};
+
+/// Describes the attributes, relationships, links, and meta of a type of resource.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Schema {
+    /// The name of the attributes of the resource.
+    pub attributes: Set<Key>,
+
+    /// The type of resource that is described by the schema.
+    pub kind: Key,
+
+    /// The name of the links of the resource object.
+    pub links: Set<Key>,
+
+    /// The name of the meta members of the resource object.
+    pub meta: Set<Key>,
+
+    /// The name and target of each relationship of the resource.
+    pub relationships: Map<Key, Relation>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl Schema {
+    /// Returns a new `Schema` for `kind` without any fields, links, or meta.
+    pub fn new(kind: Key) -> Self {
+        Schema {
+            kind,
+            attributes: Default::default(),
+            links: Default::default(),
+            meta: Default::default(),
+            relationships: Default::default(),
+            _ext: (),
+        }
+    }
+
+    /// Returns `true` if the resource has an attribute or relationship named `name`.
+    pub fn has_field(&self, name: &str) -> bool {
+        self.attributes.contains(name) || self.relationships.contains_key(name)
+    }
+}
+
+/// Describes the target of a relationship.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Relation {
+    /// A to-many relationship with resources of the given type.
+    HasMany(Key),
+
+    /// A to-one relationship with a resource of the given type.
+    HasOne(Key),
+}
+
+impl Relation {
+    /// Returns the type of the related resource(s).
+    pub fn kind(&self) -> &Key {
+        match *self {
+            Relation::HasMany(ref kind) | Relation::HasOne(ref kind) => kind,
+        }
+    }
+
+    /// Returns `true` if the relationship is a to-many relationship.
+    pub fn is_many(&self) -> bool {
+        match *self {
+            Relation::HasMany(_) => true,
+            Relation::HasOne(_) => false,
+        }
+    }
+}
+
+/// A collection of schemas, keyed by the type of resource that they describe.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::schema::{Registry, Relation, Schema};
+///
+/// let mut articles = Schema::new("articles".parse()?);
+/// let mut registry = Registry::new();
+///
+/// articles.attributes.insert("title".parse()?);
+/// articles
+///     .relationships
+///     .insert("author".parse()?, Relation::HasOne("users".parse()?));
+///
+/// registry.insert(articles);
+/// registry.insert(Schema::new("users".parse()?));
+///
+/// let target = registry.resolve("articles", &["author".parse()?]);
+/// assert_eq!(target.map(|schema| &*schema.kind), Some("users"));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Registry {
+    schemas: Map<Key, Schema>,
+}
+
+impl Registry {
+    /// Returns a new, empty registry.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the schema of `kind`, if it is in the registry.
+    pub fn get(&self, kind: &str) -> Option<&Schema> {
+        self.schemas.get(kind)
+    }
+
+    /// Adds `schema` to the registry. If the registry already contains a schema for the
+    /// same type of resource, it is replaced and the previous schema is returned.
+    pub fn insert(&mut self, schema: Schema) -> Option<Schema> {
+        self.schemas.insert(schema.kind.clone(), schema)
+    }
+
+    /// Adds the schema of the resource `T` to the registry, replacing the previous
+    /// schema of the same type of resource.
+    pub fn register<T: Resource>(&mut self) -> Result<(), Error> {
+        self.insert(T::schema()?);
+        Ok(())
+    }
+
+    /// Returns an iterator over each type of resource and its schema.
+    pub fn iter(&self) -> Iter<'_, Key, Schema> {
+        self.schemas.iter()
+    }
+
+    /// Returns the schema of the resource(s) at the end of the relationship `path`,
+    /// starting at `kind`. Returns `None` if a relationship in the path does not exist
+    /// or its target is not in the registry.
+    pub fn resolve(&self, kind: &str, path: &[Key]) -> Option<&Schema> {
+        path.iter().fold(self.get(kind), |schema, key| {
+            let relation = schema?.relationships.get(key)?;
+            self.get(relation.kind())
+        })
+    }
+}
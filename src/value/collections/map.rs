@@ -194,6 +194,34 @@ where
         self.inner.get(key)
     }
 
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::value::Map;
+    /// #
+    /// # fn main() {
+    /// let mut map = Map::new();
+    ///
+    /// map.insert("x", 1);
+    ///
+    /// if let Some(value) = map.get_mut("x") {
+    ///     *value += 1;
+    /// }
+    ///
+    /// assert_eq!(map.get("x"), Some(&2));
+    /// # }
+    /// ```
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Q: Equivalent<K> + Hash,
+    {
+        self.inner.get_mut(key)
+    }
+
     /// Inserts a key-value pair into the map.
     ///
     /// If a value already existed for key, that old value is returned in
@@ -0,0 +1,160 @@
+use error::Error;
+use value::Key;
+use value::fields::Validation;
+
+/// The casing convention of member names.
+///
+/// A [`Key`] that is parsed with [`FromStr`] or deserialized is always converted to
+/// kebab-case, which is the canonical form used to look up fields, include paths, and
+/// sort fields. A different convention can be applied explicitly with [`parse`], for
+/// example when a resource is rendered with a [`RenderPolicy`] or when a document is
+/// converted back into Rust field names with [`Flatten`]. Defaults to
+/// [`Case::Kebab`].
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::value::Key;
+/// use json_api::value::fields::Case;
+///
+/// assert_eq!("firstName".parse::<Key>()?, "first-name");
+/// assert_eq!(Case::Camel.parse("first_name")?, "firstName");
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`Case::Kebab`]: #variant.Kebab
+/// [`Flatten`]: ../../doc/struct.Flatten.html
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+/// [`Key`]: ../struct.Key.html
+/// [`RenderPolicy`]: ../../view/struct.RenderPolicy.html
+/// [`parse`]: #method.parse
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Case {
+    /// Member names are converted to kebab-case (i.e `first-name`).
+    Kebab,
+
+    /// Member names are converted to camelCase (i.e `firstName`).
+    Camel,
+
+    /// Member names are converted to snake_case (i.e `first_name`).
+    Snake,
+
+    /// Member names are validated but otherwise left as is.
+    Preserve,
+}
+
+impl Case {
+    /// Parses `source` as a member name and converts it to this casing convention.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::value::fields::Case;
+    ///
+    /// assert_eq!(Case::Kebab.parse("first_name")?, "first-name");
+    /// assert_eq!(Case::Camel.parse("first-name")?, "firstName");
+    /// assert_eq!(Case::Camel.parse("URL")?, "url");
+    /// assert_eq!(Case::Camel.parse("HTMLParser")?, "htmlParser");
+    /// assert_eq!(Case::Snake.parse("firstName")?, "first_name");
+    /// assert_eq!(Case::Preserve.parse("first_Name")?, "first_Name");
+    ///
+    /// assert!(Case::Preserve.parse("first.name").is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn parse(self, source: &str) -> Result<Key, Error> {
+        self.parse_with(source, Validation::Lenient)
+    }
+
+    /// Parses `source` as a member name with the given validation mode and converts it
+    /// to this casing convention.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::value::fields::{Case, Validation};
+    ///
+    /// assert!(Case::Camel.parse_with("first name", Validation::Lenient).is_ok());
+    /// assert!(Case::Camel.parse_with("first name", Validation::Strict).is_err());
+    /// # }
+    /// ```
+    pub fn parse_with(self, source: &str, mode: Validation) -> Result<Key, Error> {
+        let kebab = super::key::to_kebab(source, mode)?;
+        let value = match self {
+            Case::Kebab => kebab,
+            Case::Camel => to_camel(source),
+            Case::Snake => kebab.replace('-', "_"),
+            Case::Preserve => source.to_owned(),
+        };
+
+        Ok(Key::from_raw(value))
+    }
+}
+
+impl Default for Case {
+    fn default() -> Self {
+        Case::Kebab
+    }
+}
+
+/// Converts a valid member name to camelCase. A leading acronym is lowercased as a
+/// whole, every other character keeps its case unless it follows a separator.
+fn to_camel(source: &str) -> String {
+    let mut dest = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+
+    while let Some(value) = chars.peek().cloned() {
+        if !value.is_ascii_uppercase() {
+            break;
+        }
+
+        chars.next();
+
+        match chars.peek() {
+            Some(next) if next.is_ascii_lowercase() && !dest.is_empty() => {
+                dest.push(value);
+                break;
+            }
+            _ => dest.push(value.to_ascii_lowercase()),
+        }
+    }
+
+    let mut upper = false;
+
+    for value in chars {
+        match value {
+            '_' | '-' | ' ' => upper = true,
+            _ if upper => {
+                dest.push(value.to_ascii_uppercase());
+                upper = false;
+            }
+            _ => dest.push(value),
+        }
+    }
+
+    dest
+}
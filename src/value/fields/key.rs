@@ -8,11 +8,12 @@ use serde::ser::{Serialize, Serializer};
 
 use error::Error;
 use sealed::Sealed;
+use value::fields::{Case, Validation};
 
 /// Represents a single member name.
 ///
-/// When a new `Key` is parsed, the underlying value's casing convention is converted to
-/// kebab-case.
+/// When a new `Key` is parsed, the underlying value is converted to kebab-case. Use
+/// [`Case::parse`] to convert a member name to another casing convention.
 ///
 /// # Example
 ///
@@ -35,6 +36,8 @@ use sealed::Sealed;
 /// # example().unwrap()
 /// # }
 /// ```
+///
+/// [`Case::parse`]: ./fields/enum.Case.html#method.parse
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Key(String);
 
@@ -89,58 +92,7 @@ impl FromStr for Key {
     type Err = Error;
 
     fn from_str(source: &str) -> Result<Key, Self::Err> {
-        if source.is_empty() {
-            bail!("cannot be blank");
-        }
-
-        // We should reserve a bit more than what we need so in
-        // the event that we end up converting camelCase to
-        // kebab-case, we don't have to reallocate.
-        let mut dest = String::with_capacity(source.len() + 10);
-        let mut chars = source.chars().peekable();
-
-        while let Some(value) = chars.next() {
-            match value {
-                '\u{002e}'
-                | '\u{002f}'
-                | '\u{0040}'
-                | '\u{0060}'
-                | '\u{0000}'...'\u{001f}'
-                | '\u{0021}'...'\u{0029}'
-                | '\u{002a}'...'\u{002c}'
-                | '\u{003a}'...'\u{003f}'
-                | '\u{005b}'...'\u{005e}'
-                | '\u{007b}'...'\u{007f}' => {
-                    bail!("reserved '{}'", value);
-                }
-                '_' | '-' | ' ' if dest.is_empty() => {
-                    bail!("cannot start with '{}'", value);
-                }
-                '_' | '-' | ' ' => match chars.peek() {
-                    Some(&'-') | Some(&'_') | Some(&' ') | Some(&'A'...'Z') => {
-                        continue;
-                    }
-                    Some(_) => {
-                        dest.push('-');
-                    }
-                    None => {
-                        bail!("cannot end with '{}'", value);
-                    }
-                },
-                'A'...'Z' if dest.ends_with('-') => {
-                    dest.push(as_lowercase(value));
-                }
-                'A'...'Z' => {
-                    dest.push('-');
-                    dest.push(as_lowercase(value));
-                }
-                _ => {
-                    dest.push(value);
-                }
-            }
-        }
-
-        Ok(Key(dest))
+        Case::Kebab.parse(source)
     }
 }
 
@@ -199,7 +151,216 @@ impl Serialize for Key {
 
 impl Sealed for Key {}
 
+pub(super) fn to_kebab(source: &str, mode: Validation) -> Result<String, Error> {
+    let invalid = |violation| Err(Error::invalid_member_name(source, violation));
+    let strict = mode == Validation::Strict;
+
+    if source.is_empty() {
+        return invalid(Violation::Blank);
+    }
+
+    // We should reserve a bit more than what we need so in
+    // the event that we end up converting camelCase to
+    // kebab-case, we don't have to reallocate.
+    let mut dest = String::with_capacity(source.len() + 10);
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((idx, value)) = chars.next() {
+        match value {
+            '\u{002e}'
+            | '\u{002f}'
+            | '\u{0040}'
+            | '\u{0060}'
+            | '\u{0000}'...'\u{001f}'
+            | '\u{0021}'...'\u{0029}'
+            | '\u{002a}'...'\u{002c}'
+            | '\u{003a}'...'\u{003f}'
+            | '\u{005b}'...'\u{005e}'
+            | '\u{007b}'...'\u{007f}' => {
+                return invalid(Violation::Reserved(value, idx));
+            }
+            '_' | '-' | ' ' if dest.is_empty() => {
+                return invalid(Violation::Start(value, idx));
+            }
+            ' ' | '\u{0080}'...'\u{10ffff}' if strict => {
+                return invalid(Violation::NotRecommended(value, idx));
+            }
+            '_' | '-' | ' ' => match chars.peek() {
+                Some(&(_, '-'))
+                | Some(&(_, '_'))
+                | Some(&(_, ' '))
+                | Some(&(_, 'A'...'Z')) => {
+                    continue;
+                }
+                Some(_) => {
+                    dest.push('-');
+                }
+                None => {
+                    return invalid(Violation::End(value, idx));
+                }
+            },
+            'A'...'Z' if dest.is_empty() || dest.ends_with('-') => {
+                dest.push(as_lowercase(value));
+            }
+            'A'...'Z' => {
+                dest.push('-');
+                dest.push(as_lowercase(value));
+            }
+            _ => {
+                dest.push(value);
+            }
+        }
+    }
+
+    Ok(dest)
+}
+
 #[inline]
 fn as_lowercase(value: char) -> char {
     (value as u8 + 32) as char
 }
+
+/// Describes why a member name does not conform to the specification.
+///
+/// The position of an offending character is the byte offset of the character in the
+/// member name.
+///
+/// For more information, check out the *[member names]* section of the JSON API
+/// specification.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # fn main() {
+/// use json_api::error::ErrorKind;
+/// use json_api::value::Key;
+/// use json_api::value::fields::Violation;
+///
+/// match *"first.name".parse::<Key>().unwrap_err().kind() {
+///     ErrorKind::InvalidMemberName(ref name, violation) => {
+///         assert_eq!(name, "first.name");
+///         assert_eq!(violation, Violation::Reserved('.', 5));
+///     }
+///     _ => panic!("expected an invalid member name"),
+/// }
+/// # }
+/// ```
+///
+/// [member names]: http://jsonapi.org/format/#document-member-names
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Violation {
+    /// The member name is empty.
+    Blank,
+
+    /// The member name ends with a character that is only allowed between two other
+    /// characters.
+    End(char, usize),
+
+    /// The member name contains a character that is allowed but not recommended. Only
+    /// reported in [strict mode].
+    ///
+    /// [strict mode]: ./enum.Validation.html#variant.Strict
+    NotRecommended(char, usize),
+
+    /// The member name contains a character that is reserved.
+    Reserved(char, usize),
+
+    /// The member name starts with a character that is only allowed between two other
+    /// characters.
+    Start(char, usize),
+}
+
+impl Violation {
+    /// Returns the offending character, if any.
+    pub fn character(&self) -> Option<char> {
+        match *self {
+            Violation::Blank => None,
+            Violation::End(value, _)
+            | Violation::NotRecommended(value, _)
+            | Violation::Reserved(value, _)
+            | Violation::Start(value, _) => Some(value),
+        }
+    }
+
+    /// Returns the position of the offending character, if any.
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+            Violation::Blank => None,
+            Violation::End(_, idx)
+            | Violation::NotRecommended(_, idx)
+            | Violation::Reserved(_, idx)
+            | Violation::Start(_, idx) => Some(idx),
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Violation::Blank => f.write_str("cannot be blank"),
+            Violation::End(value, idx) => {
+                write!(f, "cannot end with {:?} (position {})", value, idx)
+            }
+            Violation::NotRecommended(value, idx) => {
+                write!(f, "{:?} is not recommended (position {})", value, idx)
+            }
+            Violation::Reserved(value, idx) => {
+                write!(f, "{:?} is reserved (position {})", value, idx)
+            }
+            Violation::Start(value, idx) => {
+                write!(f, "cannot start with {:?} (position {})", value, idx)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Key, Violation};
+    use error::ErrorKind;
+    use value::fields::{Case, Validation};
+
+    fn violation(source: &str) -> Option<Violation> {
+        violation_with(source, Validation::Lenient)
+    }
+
+    fn violation_with(source: &str, mode: Validation) -> Option<Violation> {
+        match Case::Kebab.parse_with(source, mode) {
+            Ok(_) => None,
+            Err(e) => match *e.kind() {
+                ErrorKind::InvalidMemberName(_, violation) => Some(violation),
+                _ => panic!("unexpected error: {}", e),
+            },
+        }
+    }
+
+    #[test]
+    fn key_violations() {
+        assert_eq!(violation(""), Some(Violation::Blank));
+        assert_eq!(violation("-name"), Some(Violation::Start('-', 0)));
+        assert_eq!(violation("name__"), Some(Violation::End('_', 5)));
+        assert_eq!(violation("first.name"), Some(Violation::Reserved('.', 5)));
+        assert_eq!(violation("über+name"), Some(Violation::Reserved('+', 5)));
+        assert_eq!(violation("first name"), None);
+        assert_eq!(violation("über"), None);
+
+    }
+
+    #[test]
+    fn key_violations_strict() {
+        let strict = |source| violation_with(source, Validation::Strict);
+
+        assert_eq!(strict("first name"), Some(Violation::NotRecommended(' ', 5)));
+        assert_eq!(strict("über"), Some(Violation::NotRecommended('ü', 0)));
+        assert_eq!(strict(" name"), Some(Violation::Start(' ', 0)));
+        assert_eq!(strict("first-name"), None);
+    }
+
+    #[test]
+    fn key_leading_uppercase() {
+        assert_eq!("Name".parse::<Key>().unwrap(), "name");
+        assert_eq!("FirstName".parse::<Key>().unwrap(), "first-name");
+    }
+}
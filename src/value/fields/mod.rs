@@ -1,7 +1,11 @@
 //! Member names and field paths.
 
+mod case;
 mod key;
 mod path;
+mod validation;
 
-pub use self::key::Key;
+pub use self::case::Case;
+pub use self::key::{Key, Violation};
 pub use self::path::{Path, Segment};
+pub use self::validation::Validation;
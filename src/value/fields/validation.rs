@@ -0,0 +1,43 @@
+/// Controls which characters are accepted when a new [`Key`] is parsed with
+/// [`Case::parse_with`].
+///
+/// Member names that contain a reserved character, or that start or end with a
+/// character that is only allowed between two other characters, are always rejected.
+/// The specification also allows a few characters that it does not recommend because
+/// they are not URL safe (space and any character above U+007F). These characters
+/// are only rejected in strict mode. Defaults to [`Validation::Lenient`], which is
+/// also the mode used by [`FromStr`] and [`Case::parse`].
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # fn main() {
+/// use json_api::value::Key;
+/// use json_api::value::fields::{Case, Validation};
+///
+/// assert!("café".parse::<Key>().is_ok());
+/// assert!(Case::Kebab.parse_with("café", Validation::Strict).is_err());
+/// # }
+/// ```
+///
+/// [`Case::parse`]: ./enum.Case.html#method.parse
+/// [`Case::parse_with`]: ./enum.Case.html#method.parse_with
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+/// [`Key`]: ../struct.Key.html
+/// [`Validation::Lenient`]: #variant.Lenient
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Validation {
+    /// Accept every character that the specification allows.
+    Lenient,
+
+    /// Reject the characters that the specification allows but does not recommend.
+    Strict,
+}
+
+impl Default for Validation {
+    fn default() -> Self {
+        Validation::Lenient
+    }
+}
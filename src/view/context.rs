@@ -1,7 +1,9 @@
 use doc::Object;
+use error::Error;
 use query::Query;
 use value::Set;
-use value::fields::{Key, Path, Segment};
+use value::fields::{Case, Key, Path, Segment};
+use view::RenderPolicy;
 
 /// A data structure containing render context that can be "forked" and passed
 /// to a child context.
@@ -23,6 +25,7 @@ pub struct Context<'v> {
     incl: &'v mut Set<Object>,
     kind: Key,
     path: Path,
+    policy: Option<&'v RenderPolicy>,
     query: Option<&'v Query>,
 }
 
@@ -59,9 +62,36 @@ impl<'v> Context<'v> {
             query,
             incl: included,
             path: Path::new(),
+            policy: None,
         }
     }
 
+    /// Creates a new, root context that enforces the limits of `policy`.
+    ///
+    /// An error is returned if the `include` parameter of `query` does not comply with
+    /// the policy. Once the context or one of its children has included the maximum
+    /// number of resources, [`try_include`] returns an error.
+    ///
+    /// [`try_include`]: #method.try_include
+    pub fn with_policy(
+        kind: Key,
+        query: Option<&'v Query>,
+        included: &'v mut Set<Object>,
+        policy: &'v RenderPolicy,
+    ) -> Result<Self, Error> {
+        if let Some(query) = query {
+            policy.check(&kind, query)?;
+        }
+
+        Ok(Context {
+            kind,
+            query,
+            incl: included,
+            path: Path::new(),
+            policy: Some(policy),
+        })
+    }
+
     /// Returns true if the field name is present in the current context's
     /// field-set or the current context's field-set does not exist.
     pub fn field(&self, name: &str) -> bool {
@@ -70,12 +100,19 @@ impl<'v> Context<'v> {
             .map_or(true, |f| f.contains(name))
     }
 
+    /// Parses `name` as a member name and converts it to the casing convention of the
+    /// context's policy, or to kebab-case if the context does not have a policy.
+    pub fn key(&self, name: &str) -> Result<Key, Error> {
+        self.policy.map_or(Case::Kebab, |policy| policy.case).parse(name)
+    }
+
     /// Creates a new child context from `self`.
     pub fn fork(&mut self, kind: Key, key: &Key) -> Context {
         Context {
             kind,
             incl: self.incl,
             path: self.path.join(key),
+            policy: self.policy,
             query: self.query,
         }
     }
@@ -89,6 +126,20 @@ impl<'v> Context<'v> {
         self.incl.insert(value)
     }
 
+    /// Adds the `value` to the context's included resource set if the set has not
+    /// reached the maximum number of included resources of the context's policy.
+    ///
+    /// Returns whether or not the value was added to the set like [`include`].
+    ///
+    /// [`include`]: #method.include
+    pub fn try_include(&mut self, value: Object) -> Result<bool, Error> {
+        if let Some(policy) = self.policy {
+            policy.check_include(self.incl, &value)?;
+        }
+
+        Ok(self.include(value))
+    }
+
     /// Returns `true` if the context is valid with respect to parent context(s).
     ///
     /// If there is no parent context (i.e the current context represents the primary
@@ -9,7 +9,9 @@
 //! [functions exported from the crate root]: ../index.html#functions
 
 mod context;
+mod policy;
 mod render;
 
 pub use self::context::Context;
+pub use self::policy::RenderPolicy;
 pub use self::render::Render;
@@ -0,0 +1,178 @@
+use doc::{Data, Document, Object, PrimaryData};
+use error::Error;
+use query::Query;
+use value::{Key, Map, Path, Set};
+use value::fields::Case;
+
+/// Limits the included resources that a client can request when a document is
+/// rendered.
+///
+/// Without a policy, a client can request any relationship path in the `include`
+/// parameter of a query, and every related resource along the path is rendered. A
+/// policy rejects a query that includes a path that is deeper than `max_depth` or that
+/// is not in the allowlist of the primary data's type, and stops rendering once the
+/// document contains more than `max_included` included resources. Each violation is
+/// an error that maps to an error object with the status `400 Bad Request`.
+///
+/// A policy also sets the casing convention of the member names that are rendered.
+///
+/// The default policy does not have any limits and renders member names in kebab-case.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # struct Post {
+/// #     id: u64,
+/// #     comments: Vec<Comment>,
+/// # }
+/// #
+/// # resource!(Post, |&self| {
+/// #     kind "posts";
+/// #     id self.id;
+/// #     has_many comments;
+/// # });
+/// #
+/// # struct Comment(u64);
+/// #
+/// # resource!(Comment, |&self| {
+/// #     kind "comments";
+/// #     id self.0;
+/// # });
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::Document;
+/// use json_api::doc::Object;
+/// use json_api::query;
+/// use json_api::view::{Render, RenderPolicy};
+///
+/// let post = Post {
+///     id: 1,
+///     comments: vec![Comment(1), Comment(2), Comment(3)],
+/// };
+///
+/// let mut policy = RenderPolicy::new();
+/// let query = query::from_str("include=comments")?;
+///
+/// policy.max_depth = Some(1);
+/// policy.max_included = Some(2);
+///
+/// let doc: Result<Document<Object>, _> = (&post).render_with(Some(&query), &policy);
+/// assert!(doc.is_err());
+///
+/// policy.max_included = Some(3);
+///
+/// let doc: Document<Object> = (&post).render_with(Some(&query), &policy)?;
+/// assert!(doc.is_ok());
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderPolicy {
+    /// The casing convention of the attributes, relationships, links, and meta of each
+    /// rendered resource.
+    pub case: Case,
+
+    /// The relationship paths that can be included when the primary data is of the
+    /// given type. The prefixes of each path (i.e `comments` for `comments.author`)
+    /// can also be included. A type that is not in the map can include any path.
+    pub includable: Map<Key, Set<Path>>,
+
+    /// The maximum number of relationships in an include path.
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of included resources in a document.
+    pub max_included: Option<usize>,
+
+    /// Private field for backwards compatibility.
+    _ext: (),
+}
+
+impl RenderPolicy {
+    /// Returns a new policy without any limits.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Checks each path in the `include` parameter of `query` against the maximum
+    /// depth and the allowlist of `kind`.
+    pub fn check(&self, kind: &Key, query: &Query) -> Result<(), Error> {
+        self.check_paths(self.includable.get(kind), query)
+    }
+
+    fn check_paths(
+        &self,
+        allowed: Option<&Set<Path>>,
+        query: &Query,
+    ) -> Result<(), Error> {
+        for path in &query.include {
+            if let Some(max) = self.max_depth {
+                if path.len() > max {
+                    return Err(Error::include_too_deep(&path.to_string(), max));
+                }
+            }
+
+            if let Some(allowed) = allowed {
+                if !allowed.iter().any(|item| item.starts_with(path)) {
+                    return Err(Error::include_not_allowed(&path.to_string()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks a document that was rendered without the policy.
+    pub(crate) fn check_doc<T>(
+        &self,
+        doc: &Document<T>,
+        query: &Query,
+    ) -> Result<(), Error>
+    where
+        T: PrimaryData,
+    {
+        let (data, included) = match *doc {
+            Document::Ok {
+                ref data,
+                ref included,
+                ..
+            } => (data, included),
+            Document::Err { .. } => return Ok(()),
+        };
+
+        let kind = match *data {
+            Data::Collection(ref data) => data.first().map(PrimaryData::kind),
+            Data::Member(ref data) => (**data).as_ref().map(PrimaryData::kind),
+        };
+
+        self.check_paths(kind.and_then(|kind| self.includable.get(kind)), query)?;
+
+        match self.max_included {
+            Some(max) if included.len() > max => Err(Error::too_many_included(max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns an error if a resource cannot be added to `included`.
+    pub(crate) fn check_include(
+        &self,
+        included: &Set<Object>,
+        value: &Object,
+    ) -> Result<(), Error> {
+        match self.max_included {
+            Some(max) if included.len() >= max && !included.contains(value) => {
+                Err(Error::too_many_included(max))
+            }
+            _ => Ok(()),
+        }
+    }
+}
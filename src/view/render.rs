@@ -1,6 +1,7 @@
 use doc::{Data, Document, PrimaryData};
 use error::Error;
 use query::Query;
+use view::RenderPolicy;
 
 /// A trait to render a given type as a document.
 ///
@@ -21,6 +22,31 @@ pub trait Render<T: PrimaryData> {
     /// [`Resource`]: ../trait.Resource.html
     /// [`resource!`]: ../macro.resource.html
     fn render(self, query: Option<&Query>) -> Result<Document<T>, Error>;
+
+    /// Attempts to render the given type as a document with the limits of `policy`.
+    ///
+    /// Types that implement the [`Resource`] trait stop rendering as soon as a limit is
+    /// exceeded. The default implementation renders the document with [`render`] and
+    /// then checks it against the policy.
+    ///
+    /// [`Resource`]: ../trait.Resource.html
+    /// [`render`]: #tymethod.render
+    fn render_with(
+        self,
+        query: Option<&Query>,
+        policy: &RenderPolicy,
+    ) -> Result<Document<T>, Error>
+    where
+        Self: Sized,
+    {
+        let doc = self.render(query)?;
+
+        if let Some(query) = query {
+            policy.check_doc(&doc, query)?;
+        }
+
+        Ok(doc)
+    }
 }
 
 impl<D, T> Render<D> for Option<T>
@@ -29,15 +55,25 @@ where
     T: Render<D> + Sized,
 {
     fn render(self, query: Option<&Query>) -> Result<Document<D>, Error> {
-        match self {
-            Some(value) => value.render(query),
-            None => Ok(Document::Ok {
+        self.render_with(query, &Default::default())
+    }
+
+    fn render_with(
+        self,
+        query: Option<&Query>,
+        policy: &RenderPolicy,
+    ) -> Result<Document<D>, Error> {
+        let value = match self {
+            Some(value) => return value.render_with(query, policy),
+            None => Document::Ok {
                 data: Data::Member(Box::new(None)),
                 included: Default::default(),
                 jsonapi: Default::default(),
                 links: Default::default(),
                 meta: Default::default(),
-            }),
-        }
+            },
+        };
+
+        value.render_with(query, policy)
     }
 }
@@ -0,0 +1,1059 @@
+#[macro_use]
+extern crate json_api;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+
+use json_api::doc::atomic::{Operation, OperationData, OperationResult, Operations, Processor};
+use json_api::doc::{self, borrowed, validate, Cycle, Data, Document, ErrorObject, ErrorSource,
+                    Extractor, Flatten, FromNewObject, Identifier, NewObject, Object, Version};
+use json_api::http::StatusCode;
+use json_api::error::ErrorKind;
+use json_api::openapi::OpenApi;
+use json_api::query::{self, Query};
+use json_api::schema::{Registry, Relation};
+use json_api::value::Key;
+use json_api::value::fields::Case;
+use json_api::view::{Render, RenderPolicy};
+
+const V1_0: &str = r#"{
+    "data": {
+        "type": "articles",
+        "id": "1",
+        "attributes": { "title": "Rails is Omakase" },
+        "links": { "self": "http://example.com/articles/1" }
+    },
+    "jsonapi": { "version": "1.0" }
+}"#;
+
+const V1_1: &str = r#"{
+    "data": {
+        "type": "articles",
+        "lid": "local-1",
+        "attributes": { "title": "Rails is Omakase" },
+        "relationships": {
+            "author": {
+                "data": { "type": "people", "id": "9", "lid": "local-2" }
+            }
+        },
+        "links": {
+            "self": {
+                "href": "http://example.com/articles/1",
+                "rel": "self",
+                "title": "Rails is Omakase",
+                "type": "text/html",
+                "hreflang": ["en", "fr"]
+            }
+        }
+    },
+    "jsonapi": {
+        "version": "1.1",
+        "ext": ["https://jsonapi.org/ext/atomic"],
+        "profile": ["http://example.com/profiles/flexible-pagination"]
+    }
+}"#;
+
+#[test]
+fn doc_v1_0_round_trip() {
+    let doc: Document<Object> = serde_json::from_str(V1_0).unwrap();
+    let json = serde_json::to_string(&doc).unwrap();
+
+    assert_eq!(doc.jsonapi().version, Version::V1);
+    assert_eq!(doc, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn doc_v1_1_round_trip() {
+    let doc: Document<NewObject> = serde_json::from_str(V1_1).unwrap();
+    let json = serde_json::to_string(&doc).unwrap();
+
+    let again: Document<NewObject> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(doc.jsonapi().version, Version::V1_1);
+    assert_eq!(doc.required_version(), Version::V1_1);
+    assert_eq!(serde_json::to_string(&again).unwrap(), json);
+}
+
+#[test]
+fn doc_v1_1_members_require_declaration() {
+    let source = V1_1.replace(r#""version": "1.1""#, r#""version": "1.0""#);
+    assert!(serde_json::from_str::<Document<NewObject>>(&source).is_err());
+
+    let source = r#"{ "data": { "type": "people", "id": "9", "lid": "local-2" } }"#;
+    assert!(serde_json::from_str::<Document<Identifier>>(source).is_err());
+    let source = r#"{
+        "data": {
+            "type": "articles",
+            "id": "1",
+            "relationships": {
+                "author": { "data": { "type": "people", "lid": "local-2" } }
+            }
+        }
+    }"#;
+    assert!(serde_json::from_str::<Document<Object>>(source).is_err());
+
+    let source = source.replace(r#""lid": "local-2""#, r#""id": "9""#);
+    assert!(serde_json::from_str::<Document<Object>>(&source).is_ok());
+}
+
+#[test]
+fn doc_identifier_requires_id_or_lid() {
+    assert!(serde_json::from_str::<Identifier>(r#"{ "type": "people" }"#).is_err());
+
+    let source = r#"{
+        "data": {
+            "type": "articles",
+            "id": "1",
+            "relationships": {
+                "author": { "data": { "type": "people" } }
+            }
+        }
+    }"#;
+    assert!(serde_json::from_str::<Document<Object>>(source).is_err());
+
+    let source = r#"{ "type": "people", "lid": "a" }"#;
+    let ident: Identifier = serde_json::from_str(source).unwrap();
+    assert!(ident.id.is_empty());
+    assert_eq!(ident.lid, Some("a".to_owned()));
+}
+
+const ATOMIC: &str = r#"{
+    "atomic:operations": [{
+        "op": "add",
+        "data": {
+            "type": "people",
+            "lid": "author",
+            "attributes": { "name": "dgeb" }
+        }
+    }, {
+        "op": "add",
+        "data": {
+            "type": "articles",
+            "attributes": { "title": "JSON API paints my bikeshed!" },
+            "relationships": {
+                "author": {
+                    "data": { "type": "people", "lid": "author" }
+                }
+            }
+        }
+    }, {
+        "op": "update",
+        "ref": { "type": "articles", "id": "13", "relationship": "author" },
+        "data": { "type": "people", "lid": "author" }
+    }],
+    "jsonapi": { "version": "1.1", "ext": ["https://jsonapi.org/ext/atomic"] }
+}"#;
+
+struct Store {
+    linked: Vec<String>,
+    next: u64,
+}
+
+impl Processor for Store {
+    fn apply(&mut self, op: Operation) -> Result<OperationResult, Vec<ErrorObject>> {
+        match op {
+            Operation::Add {
+                data: OperationData::Resource(object),
+                ..
+            } => {
+                for rel in object.relationships.values() {
+                    if let Data::Member(ref data) = rel.data {
+                        self.linked.extend(data.iter().map(|ident| ident.id.clone()));
+                    }
+                }
+
+                self.next += 1;
+                Ok(OperationResult::new(Some(Object::new(
+                    object.kind.clone(),
+                    self.next.to_string(),
+                ))))
+            }
+            _ => {
+                let mut error = ErrorObject::new(Some(StatusCode::NOT_FOUND));
+
+                error.source = Some(ErrorSource::new(None, Some("/ref".to_owned())));
+                Err(vec![error])
+            }
+        }
+    }
+}
+
+#[test]
+fn atomic_round_trip() {
+    let doc: Operations = serde_json::from_str(ATOMIC).unwrap();
+    let json = serde_json::to_string(&doc).unwrap();
+    let again: Operations = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(doc.operations.len(), 3);
+    assert_eq!(serde_json::to_string(&again).unwrap(), json);
+
+    match doc.operations[2] {
+        Operation::Update {
+            data: OperationData::Linkage(_),
+            ref target,
+            ..
+        } => {
+            let target = target.as_ref().unwrap();
+
+            assert_eq!(target.ident.id, "13");
+            assert_eq!(target.relationship, Some("author".parse().unwrap()));
+        }
+        ref op => panic!("unexpected operation {:?}", op),
+    }
+
+    let source = ATOMIC.replace(r#""version": "1.1""#, r#""version": "1.0""#);
+    assert!(serde_json::from_str::<Operations>(&source).is_err());
+    let source = r#"{
+        "atomic:operations": [{
+            "op": "remove",
+            "ref": { "type": "articles", "id": "13" }
+        }]
+    }"#;
+    let doc: Operations = serde_json::from_str(source).unwrap();
+    assert_eq!(doc.jsonapi.version, Version::V1_1);
+
+    // An explicit null clears a to-one relationship.
+    let source = r#"{
+        "atomic:operations": [{
+            "op": "update",
+            "ref": { "type": "articles", "id": "1", "relationship": "author" },
+            "data": null
+        }]
+    }"#;
+    let doc: Operations = serde_json::from_str(source).unwrap();
+
+    match doc.operations[0] {
+        Operation::Update {
+            data: OperationData::Linkage(Data::Member(ref data)),
+            ..
+        } => assert!(data.is_none()),
+        ref op => panic!("unexpected operation {:?}", op),
+    }
+
+    let json = serde_json::to_string(&doc).unwrap();
+    assert!(json.contains(r#""data":null"#));
+
+    let source = source.replace(r#""data": null"#, "");
+    let source = source.replace(r#""author" },"#, r#""author" }"#);
+    assert!(serde_json::from_str::<Operations>(&source).is_err());
+}
+
+#[test]
+fn atomic_process() {
+    let doc: Operations = serde_json::from_str(ATOMIC).unwrap();
+    let mut store = Store {
+        linked: Vec::new(),
+        next: 0,
+    };
+
+    match store.process(doc) {
+        Err(Document::Err { errors, jsonapi, .. }) => {
+            let source = errors[0].source.as_ref().unwrap();
+
+            assert_eq!(jsonapi.version, Version::V1_1);
+            assert_eq!(source.pointer, Some("/atomic:operations/2/ref".to_owned()));
+        }
+        _ => panic!("expected an error document"),
+    }
+
+    // The lid of the first operation should resolve to the id it was assigned.
+    assert_eq!(store.linked, vec!["1".to_owned()]);
+}
+
+const COMPOUND: &str = r#"{
+    "data": [{
+        "type": "articles",
+        "id": "1",
+        "relationships": {
+            "author": { "data": { "type": "people", "id": "9" } }
+        }
+    }, {
+        "type": "articles",
+        "id": "1"
+    }],
+    "included": [{
+        "type": "people",
+        "id": "9",
+        "relationships": {
+            "avatar": { "data": { "type": "images", "id": "2" } }
+        }
+    }, {
+        "type": "images",
+        "id": "2"
+    }, {
+        "type": "comments",
+        "id": "5",
+        "attributes": { "id": "5" }
+    }]
+}"#;
+
+fn pointers(errors: &[ErrorObject]) -> Vec<&str> {
+    errors
+        .iter()
+        .filter_map(|error| error.source.as_ref())
+        .filter_map(|source| source.pointer.as_ref())
+        .map(String::as_str)
+        .collect()
+}
+
+#[test]
+fn doc_validate() {
+    let doc: Document<Object> = serde_json::from_str(COMPOUND).unwrap();
+    let errors = doc.validate();
+
+    assert_eq!(
+        pointers(&errors),
+        vec!["/data/1", "/included/2/attributes/id", "/included/2"]
+    );
+}
+
+#[test]
+fn doc_validate_from_str() {
+    let errors = validate::from_str(COMPOUND).unwrap();
+    assert_eq!(errors.len(), 3);
+
+    let errors = validate::from_str(r#"{ "data": null, "errors": [] }"#).unwrap();
+    assert_eq!(pointers(&errors), vec![""]);
+
+    let errors = validate::from_str(r#"{ "meta": {}, "jsonapi": { "version": "2.0" } }"#).unwrap();
+    assert_eq!(pointers(&errors), vec!["/jsonapi/version"]);
+
+    let source = r#"{
+        "data": [{ "type": "people", "id": "9" }],
+        "included": [{ "type": "people", "id": "9" }, { "type": "people", "id": "9" }]
+    }"#;
+
+    let errors = validate::from_str(source).unwrap();
+    assert_eq!(pointers(&errors), vec!["/included/1"]);
+    let errors = validate::from_str(r#"{ "meta": { "a": 1 } }"#).unwrap();
+    assert!(errors.is_empty());
+
+    let source = r#"{ "meta": { "a": 1 }, "jsonapi": { "version": "1.0", "profile": ["/p"] } }"#;
+    let errors = validate::from_str(source).unwrap();
+    assert_eq!(pointers(&errors), vec!["/jsonapi/version"]);
+
+    let source = r#"{
+        "data": {
+            "type": "articles",
+            "id": "1",
+            "attributes": { "links": [], "relationships": 2 }
+        }
+    }"#;
+    assert!(validate::from_str(source).unwrap().is_empty());
+}
+
+const GRAPH: &str = r#"{
+    "data": [{
+        "type": "articles",
+        "id": "1",
+        "relationships": {
+            "author": { "data": { "type": "people", "id": "9" } },
+            "comments": {
+                "data": [
+                    { "type": "comments", "id": "6" },
+                    { "type": "comments", "id": "7" },
+                    { "type": "comments", "id": "5" }
+                ]
+            },
+            "next": { "data": { "type": "articles", "id": "2" } }
+        }
+    }, {
+        "type": "articles",
+        "id": "2",
+        "relationships": {
+            "author": { "data": { "type": "people", "id": "10" } },
+            "comments": { "data": [] }
+        }
+    }],
+    "included": [{
+        "type": "people",
+        "id": "9"
+    }, {
+        "type": "comments",
+        "id": "5"
+    }, {
+        "type": "comments",
+        "id": "6"
+    }]
+}"#;
+
+#[test]
+fn doc_graph() {
+    let doc: Document<Object> = serde_json::from_str(GRAPH).unwrap();
+    let graph = doc.graph();
+    let first = Identifier::new("articles".parse().unwrap(), "1".to_owned());
+    let second = Identifier::new("articles".parse().unwrap(), "2".to_owned());
+
+    assert_eq!(graph.len(), 5);
+
+    // A to-one relationship can point to other primary data.
+    let next = graph.related(&first, "next").unwrap();
+    assert_eq!(next.id, "2");
+
+    // Resources that are not in the document are not resolved.
+    assert!(graph.related(&second, "author").is_none());
+    assert!(graph.related(&first, "missing").is_none());
+    assert!(graph.related(&first, "comments").is_none());
+
+    let missing = Identifier::new("articles".parse().unwrap(), "3".to_owned());
+    assert!(graph.related(&missing, "author").is_none());
+    assert!(graph.related_many(&missing, "comments").is_empty());
+
+    // To-many relationships keep the order of the linkage and skip missing resources.
+    let comments = graph.related_many(&first, "comments");
+    let ids = comments.iter().map(|item| &*item.id).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["6", "5"]);
+
+    assert!(graph.related_many(&second, "comments").is_empty());
+    assert_eq!(doc.related_many(&first, "author").len(), 1);
+    assert_eq!(doc.related(&first, "author").map(|item| &*item.id), Some("9"));
+}
+
+const CYCLE: &str = r#"{
+    "data": {
+        "type": "articles",
+        "id": "1",
+        "attributes": { "title": "Rails is Omakase" },
+        "relationships": {
+            "author": { "data": { "type": "people", "id": "9" } }
+        }
+    },
+    "included": [{
+        "type": "people",
+        "id": "9",
+        "relationships": {
+            "articles": { "data": [{ "type": "articles", "id": "1" }] },
+            "profile": { "data": { "type": "profiles", "id": "3" } }
+        }
+    }, {
+        "type": "profiles",
+        "id": "3",
+        "attributes": { "bio": "Ember core team" }
+    }]
+}"#;
+
+#[test]
+fn doc_flatten_cycle() {
+    let value: serde_json::Value = json_api::from_str::<Object, _>(CYCLE).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "id": "1",
+            "title": "Rails is Omakase",
+            "author": {
+                "id": "9",
+                "articles": ["1"],
+                "profile": { "id": "3", "bio": "Ember core team" },
+            },
+        })
+    );
+
+    let mut options = Flatten::new();
+    options.cycle = Cycle::Null;
+
+    let value = json_api::doc::from_str_with::<Object, serde_json::Value>(CYCLE, options);
+    assert_eq!(value.unwrap()["author"]["articles"], json!([null]));
+}
+
+#[test]
+fn doc_flatten_max_depth() {
+    let mut options = Flatten::new();
+    let mut flatten = |max_depth| {
+        options.max_depth = max_depth;
+        json_api::doc::from_str_with::<Object, serde_json::Value>(CYCLE, options).unwrap()
+    };
+
+    assert_eq!(flatten(Some(0))["author"], json!("9"));
+    assert_eq!(flatten(Some(1))["author"]["profile"], json!("3"));
+    assert_eq!(flatten(Some(2))["author"]["profile"]["bio"], json!("Ember core team"));
+}
+
+struct Author {
+    id: u64,
+    name: String,
+}
+
+resource!(Author, |&self| {
+    kind "people";
+    id self.id;
+    attrs name;
+});
+
+struct Post {
+    id: u64,
+    title: String,
+    author: Option<Author>,
+}
+
+resource!(Post, |&self| {
+    kind "posts";
+    id self.id;
+    attrs title;
+    has_one author;
+});
+
+fn posts() -> Vec<Post> {
+    (1..5)
+        .map(|id| Post {
+            id,
+            title: format!("Post {}", id),
+            author: Some(Author {
+                id: id % 2,
+                name: format!("Author {}", id % 2),
+            }),
+        })
+        .collect()
+}
+
+#[test]
+fn doc_render_policy() {
+    let posts = posts();
+    let query = query::from_str("include=author").unwrap();
+    let render = |policy: &RenderPolicy| -> Result<Document<Object>, _> {
+        (&*posts).render_with(Some(&query), policy)
+    };
+
+    let mut policy = RenderPolicy::new();
+
+    match render(&policy).unwrap() {
+        Document::Ok { included, .. } => assert_eq!(included.len(), 2),
+        Document::Err { .. } => panic!("expected a document without errors"),
+    }
+
+    policy.max_included = Some(1);
+
+    let e = render(&policy).unwrap_err();
+    let error = ErrorObject::from(&e);
+
+    assert!(match *e.kind() {
+        ErrorKind::TooManyIncluded(1) => true,
+        _ => false,
+    });
+    assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+    assert_eq!(
+        error.source.and_then(|source| source.parameter),
+        Some("include".to_owned())
+    );
+
+    policy.max_included = None;
+    policy.max_depth = Some(0);
+
+    assert!(match *render(&policy).unwrap_err().kind() {
+        ErrorKind::IncludeTooDeep(ref path, 0) => path == "author",
+        _ => false,
+    });
+
+    policy.max_depth = None;
+    policy
+        .includable
+        .insert("posts".parse().unwrap(), "title".parse().unwrap());
+
+    assert!(match *render(&policy).unwrap_err().kind() {
+        ErrorKind::IncludeNotAllowed(ref path) => path == "author",
+        _ => false,
+    });
+
+    policy
+        .includable
+        .insert("posts".parse().unwrap(), "author.posts".parse().unwrap());
+
+    assert!(render(&policy).is_ok());
+
+    let doc = render(&RenderPolicy::new()).unwrap();
+    assert!(doc.render_with(Some(&query), &policy).is_ok());
+
+    policy.max_included = Some(1);
+
+    let doc = render(&RenderPolicy::new()).unwrap();
+    assert!(doc.render_with(Some(&query), &policy).is_err());
+}
+
+#[test]
+fn doc_to_writer_iter() {
+    let posts = posts();
+    let query = Query::builder().include("author").build().unwrap();
+
+    for query in &[None, Some(&query)] {
+        let mut buf = Vec::new();
+        let expected = json_api::to_vec::<_, Object>(&posts[..], *query).unwrap();
+
+        json_api::doc::to_writer_iter(&mut buf, &posts, *query).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), String::from_utf8(expected).unwrap());
+
+        let mut buf = Vec::new();
+        let expected = json_api::to_vec_pretty::<_, Object>(&posts[..], *query).unwrap();
+
+        json_api::doc::to_writer_iter_pretty(&mut buf, &posts, *query).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), String::from_utf8(expected).unwrap());
+    }
+
+    let mut buf = Vec::new();
+    let empty: Vec<Post> = Vec::new();
+
+    json_api::doc::to_writer_iter(&mut buf, empty, None).unwrap();
+    assert_eq!(buf, json_api::to_vec::<_, Object>(&[][..] as &[Post], None).unwrap());
+}
+
+#[test]
+fn doc_borrowed() {
+    let doc = borrowed::from_str(COMPOUND).unwrap();
+
+    match doc {
+        borrowed::Document::Ok { ref data, ref included, .. } => {
+            for item in data.iter().chain(included) {
+                assert!(match item.kind {
+                    Cow::Borrowed(_) => true,
+                    Cow::Owned(_) => false,
+                });
+            }
+        }
+        borrowed::Document::Err { .. } => panic!("expected a document without errors"),
+    }
+
+    let owned: Document<Object> = serde_json::from_str(COMPOUND).unwrap();
+    assert_eq!(doc.into_owned::<Object>().unwrap(), owned);
+
+    let source = r#"{ "type": "people", "id": "9", "attributes": { "firstName": "Dan\n" } }"#;
+    let item: borrowed::Object = serde_json::from_str(source).unwrap();
+
+    let name = borrowed::Value::String("Dan\n".into());
+
+    assert_eq!(item.attributes.get("firstName"), Some(&name));
+    assert!(item.into_owned::<Object>().unwrap().attributes.contains_key("first-name"));
+    let source = r#"{ "data": null, "links": { "self": "/a" }, "foo": [1, { "b": 2 }] }"#;
+
+    match borrowed::from_str(source).unwrap() {
+        borrowed::Document::Ok { data, links, .. } => {
+            assert_eq!(data.iter().count(), 0);
+            assert!(match links.keys().next() {
+                Some(&Cow::Borrowed("self")) => true,
+                _ => false,
+            });
+        }
+        borrowed::Document::Err { .. } => panic!("expected a document without errors"),
+    }
+
+    assert!(borrowed::from_str(r#"{ "data": null, "errors": [] }"#).is_err());
+}
+
+#[test]
+fn doc_builder() {
+    let posts = posts();
+    let query = Query::builder().include("author").build().unwrap();
+    let doc: Document<Object> = Document::builder()
+        .data(&posts[..], Some(&query))
+        .link("self", "https://example.com/posts".parse().unwrap())
+        .meta("total-count", 4)
+        .build()
+        .unwrap();
+
+    let expected = json_api::to_doc::<_, Object>(&posts[..], Some(&query)).unwrap();
+
+    match (doc, expected) {
+        (
+            Document::Ok { data, included, links, meta, .. },
+            Document::Ok { data: expected_data, included: expected_included, .. },
+        ) => {
+            assert_eq!(data, expected_data);
+            assert_eq!(included, expected_included);
+            assert_eq!(*links.get("self").unwrap(), "https://example.com/posts");
+            assert_eq!(meta.get("total-count"), Some(&4.into()));
+        }
+        _ => panic!("expected a document without errors"),
+    }
+
+    let mut error = ErrorObject::new(Some(StatusCode::NOT_FOUND));
+    error.title = Some("Not Found".to_owned());
+
+    let doc = Document::<Object>::builder()
+        .error(error.clone())
+        .meta("request-id", "abc")
+        .build()
+        .unwrap();
+
+    match doc {
+        Document::Err { errors, meta, .. } => {
+            assert_eq!(errors, vec![error.clone()]);
+            assert_eq!(meta.get("request-id"), Some(&"abc".into()));
+        }
+        _ => panic!("expected a document with errors"),
+    }
+
+    let result = Document::<Object>::builder()
+        .data(&posts[..], None)
+        .error(error)
+        .build();
+
+    assert!(result.is_err());
+}
+
+struct Person {
+    id: u64,
+    first_name: String,
+}
+
+resource!(Person, |&self| {
+    kind "people";
+    id self.id;
+    attrs first_name;
+    meta "lastLogin", { "2018-01-01" }
+});
+
+#[test]
+fn doc_case() {
+    let person = Person {
+        id: 1,
+        first_name: "Dan".to_owned(),
+    };
+
+    let render = |case: Case| -> Document<Object> {
+        let mut policy = RenderPolicy::new();
+        policy.case = case;
+
+        (&person).render_with(None, &policy).unwrap()
+    };
+
+    for &(case, name, login) in &[
+        (Case::Kebab, "first-name", "last-login"),
+        (Case::Camel, "firstName", "lastLogin"),
+        (Case::Snake, "first_name", "last_login"),
+        (Case::Preserve, "first_name", "lastLogin"),
+    ] {
+        match render(case) {
+            Document::Ok { data: Data::Member(data), meta, .. } => {
+                assert!(data.unwrap().attributes.contains_key(name));
+                assert!(meta.contains_key(login));
+            }
+            _ => panic!("expected a document with a single resource"),
+        }
+    }
+
+    let source = r#"{
+        "data": {
+            "type": "people",
+            "id": "1",
+            "attributes": { "firstName": "Dan", "home_town": "Boston" }
+        }
+    }"#;
+
+    let doc = serde_json::from_str::<Document<Object>>(source).unwrap();
+
+    match doc {
+        Document::Ok { data: Data::Member(ref data), .. } => {
+            let item = (**data).as_ref().unwrap();
+
+            assert!(item.attributes.contains_key("first-name"));
+            assert!(item.attributes.contains_key("home-town"));
+        }
+        _ => panic!("expected a document with a single resource"),
+    }
+
+    let mut options = Flatten::new();
+    options.rename = Some(Case::Snake);
+
+    let value: serde_json::Value = json_api::doc::from_doc_with(doc, options).unwrap();
+    assert_eq!(value, json!({ "id": "1", "first_name": "Dan", "home_town": "Boston" }));
+}
+
+#[derive(Debug, Deserialize)]
+struct Article {
+    title: String,
+}
+
+fn error_source<U>(source: &str) -> (Option<String>, Option<String>)
+where
+    U: Debug + DeserializeOwned,
+{
+    let e = json_api::from_str::<NewObject, U>(source).unwrap_err();
+    let error = ErrorObject::from(&e);
+
+    assert_eq!(error.status, Some(StatusCode::BAD_REQUEST));
+    (error.title, error.source.and_then(|source| source.pointer))
+}
+
+#[test]
+fn doc_error_pointer() {
+    let pointer = |source| error_source::<Article>(source).1;
+
+    assert_eq!(
+        pointer(r#"{ "data": { "type": "articles", "attributes": { "title": 1 } } }"#),
+        Some("/data/attributes/title".to_owned())
+    );
+
+    assert_eq!(
+        error_source::<Vec<Article>>(r#"{
+            "data": [
+                { "type": "articles", "attributes": { "title": "One" } },
+                { "type": "articles", "attributes": {} }
+            ]
+        }"#).1,
+        Some("/data/1".to_owned())
+    );
+
+    assert_eq!(
+        pointer(r#"{ "data": { "type": "articles", "attributes": { "bad/key": 1 } } }"#),
+        Some("/data/attributes/bad~1key".to_owned())
+    );
+
+    assert_eq!(
+        pointer(r#"{ "data": { "attributes": { "title": "One" } } }"#),
+        Some("/data".to_owned())
+    );
+
+    assert_eq!(
+        pointer(r#"{ "data": null, "jsonapi": { "version": "2.0" } }"#),
+        Some("/jsonapi/version".to_owned())
+    );
+
+    let (title, pointer) = error_source::<Article>(r#"{ "data": "#);
+
+    assert_eq!(title, Some("Invalid JSON".to_owned()));
+    assert_eq!(pointer, None);
+
+    let article = json_api::from_str::<NewObject, Article>(r#"{
+        "data": { "type": "articles", "attributes": { "title": "One" } }
+    }"#).unwrap();
+
+    assert_eq!(article.title, "One");
+
+    let e = json_api::Error::from(serde_json::from_str::<u8>(r#""one""#).unwrap_err());
+    let error = ErrorObject::from(&e);
+
+    assert_eq!(error.status, Some(StatusCode::INTERNAL_SERVER_ERROR));
+
+    let e = "first.name".parse::<Key>().unwrap_err();
+    let error = ErrorObject::from(&e);
+
+    assert_eq!(error.status, Some(StatusCode::INTERNAL_SERVER_ERROR));
+    assert_eq!(error.detail, None);
+}
+
+#[derive(Debug)]
+struct NewComment {
+    body: String,
+    article: Option<u64>,
+    tags: Vec<String>,
+}
+
+impl FromNewObject for NewComment {
+    fn from_new_object(object: NewObject) -> Result<Self, json_api::Error> {
+        let mut extract = Extractor::from_new_object(object, "comments")?;
+
+        Ok(NewComment {
+            body: extract.attribute("body")?,
+            article: extract.has_one("article", "articles")?,
+            tags: extract.has_many("tags", "tags")?,
+        })
+    }
+}
+
+#[test]
+fn doc_from_new_object() {
+    let comment: NewComment = doc::new_object_from_str(r#"{
+        "data": {
+            "type": "comments",
+            "attributes": { "body": "First!" },
+            "relationships": {
+                "article": { "data": { "type": "articles", "id": "1" } },
+                "tags": { "data": [{ "type": "tags", "id": "meta" }] }
+            }
+        }
+    }"#).unwrap();
+
+    assert_eq!(comment.body, "First!");
+    assert_eq!(comment.article, Some(1));
+    assert_eq!(comment.tags, vec!["meta".to_owned()]);
+
+    let pointer = |source: &str| {
+        let e = doc::new_object_from_str::<NewComment>(source).unwrap_err();
+        ErrorObject::from(&e).source.and_then(|source| source.pointer)
+    };
+
+    assert_eq!(
+        pointer(r#"{ "data": { "type": "articles", "attributes": { "body": "" } } }"#),
+        Some("/data/type".to_owned())
+    );
+
+    assert_eq!(
+        pointer(r#"{ "data": { "type": "comments", "attributes": {} } }"#),
+        Some("/data/attributes".to_owned())
+    );
+
+    assert_eq!(
+        pointer(r#"{
+            "data": {
+                "type": "comments",
+                "attributes": { "body": "First!" },
+                "relationships": {
+                    "article": { "data": { "type": "people", "id": "1" } }
+                }
+            }
+        }"#),
+        Some("/data/relationships/article/data/type".to_owned())
+    );
+
+    assert_eq!(
+        pointer(r#"{
+            "data": {
+                "type": "comments",
+                "attributes": { "body": "First!" },
+                "relationships": {
+                    "article": { "data": { "type": "articles", "id": "one" } }
+                }
+            }
+        }"#),
+        Some("/data/relationships/article/data/id".to_owned())
+    );
+
+    assert_eq!(
+        pointer(r#"{
+            "data": {
+                "type": "comments",
+                "attributes": { "body": "First!" },
+                "relationships": {
+                    "tags": {
+                        "data": [{ "type": "tags", "id": "a" }, { "type": "x", "id": "b" }]
+                    }
+                }
+            }
+        }"#),
+        Some("/data/relationships/tags/data/1/type".to_owned())
+    );
+
+    assert_eq!(
+        pointer(r#"{ "data": [] }"#),
+        Some("/data".to_owned())
+    );
+}
+
+#[test]
+fn doc_extract_missing_or_null() {
+    let object: Object = doc::object_from_str(r#"{
+        "data": {
+            "type": "comments",
+            "id": "1",
+            "attributes": { "body": null },
+            "relationships": {
+                "article": { "data": null },
+                "tags": { "data": [] }
+            }
+        }
+    }"#).unwrap();
+
+    let mut extract = Extractor::from_object(object, "comments").unwrap();
+
+    assert!(extract.contains("body"));
+    assert!(extract.contains("article"));
+    assert!(!extract.contains("edited-at"));
+    assert!(!extract.contains("author"));
+
+    let body: Option<Option<String>> = extract.optional_attribute("body").unwrap();
+    let edited: Option<Option<String>> = extract.optional_attribute("edited-at").unwrap();
+
+    assert_eq!(body, Some(None));
+    assert_eq!(edited, None);
+
+    let article = extract.optional_has_one::<u64>("article", "articles").unwrap();
+    let author = extract.optional_has_one::<u64>("author", "people").unwrap();
+
+    assert_eq!(article, Some(None));
+    assert_eq!(author, None);
+
+    let tags = extract.optional_has_many::<String>("tags", "tags").unwrap();
+    let likes = extract.optional_has_many::<u64>("likes", "people").unwrap();
+
+    assert_eq!(tags, Some(Vec::new()));
+    assert_eq!(likes, None);
+    assert!(!extract.contains("body"));
+}
+
+#[test]
+fn doc_resource_schema() {
+    let mut registry = Registry::new();
+
+    registry.register::<Author>().unwrap();
+    registry.register::<Post>().unwrap();
+
+    let posts = registry.get("posts").unwrap();
+
+    assert!(posts.attributes.contains("title"));
+    assert_eq!(
+        posts.relationships.get("author"),
+        Some(&Relation::HasOne("people".parse().unwrap()))
+    );
+
+    let author = registry.resolve("posts", &["author".parse().unwrap()]);
+    assert_eq!(author.map(|schema| &*schema.kind), Some("people"));
+
+    let query = query::from_str("include=author&fields[people]=name").unwrap();
+    assert!(query.validate("posts", &registry).is_empty());
+
+    let query = query::from_str("include=comments&fields[people]=email").unwrap();
+    assert_eq!(query.validate("posts", &registry).len(), 2);
+}
+
+#[test]
+fn doc_openapi() {
+    fn refs<'a>(value: &'a serde_json::Value, found: &mut Vec<&'a str>) {
+        match *value {
+            serde_json::Value::Array(ref items) => {
+                for item in items {
+                    refs(item, found);
+                }
+            }
+            serde_json::Value::Object(ref map) => {
+                for (key, item) in map {
+                    match (key.as_str(), item.as_str()) {
+                        ("$ref", Some(target)) => found.push(target),
+                        _ => refs(item, found),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut registry = Registry::new();
+
+    registry.register::<Author>().unwrap();
+    registry.register::<Post>().unwrap();
+
+    let spec = OpenApi::new("Blog", "1.0.0").to_json(&registry);
+    let paths = spec["paths"].as_object().unwrap();
+
+    assert_eq!(spec["openapi"], "3.0.3");
+    assert!(paths.contains_key("/posts"));
+    assert!(paths.contains_key("/posts/{id}/author"));
+    assert!(paths["/posts/{id}/relationships/author"]["post"].is_null());
+    assert_eq!(
+        spec["components"]["schemas"]["posts.Relationships"]["properties"]["author"]
+            ["properties"]["data"]["oneOf"][0]["allOf"][1]["properties"]["type"]["enum"],
+        json!(["people"])
+    );
+
+    let schemas = &spec["components"]["schemas"];
+
+    assert_eq!(
+        schemas["posts.Relationships"]["properties"]["author"]["properties"]["data"]
+            ["oneOf"][1],
+        json!({ "$ref": "#/components/schemas/Null" })
+    );
+    assert_eq!(schemas["Identifier"]["required"], json!(["type"]));
+    assert!(schemas["Identifier"]["properties"]["lid"].is_object());
+
+    let mut found = Vec::new();
+    refs(&spec, &mut found);
+
+    for target in found {
+        assert!(target.starts_with('#'), "{}", target);
+        assert!(spec.pointer(&target[1..]).is_some(), "{}", target);
+    }
+}
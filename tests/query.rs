@@ -1,9 +1,14 @@
 extern crate json_api;
 #[macro_use]
 extern crate ordermap;
+extern crate rusqlite;
 
 use json_api::Error;
-use json_api::query::{self, Direction, Query};
+use json_api::doc::{Identifier, Object, Relationship};
+use json_api::query::{self, sql, Direction, Filter, Operator, Overflow, Query,
+                       QueryPolicy};
+use json_api::schema::{Registry, Relation, Schema};
+use json_api::value::{Path, Value};
 use ordermap::OrderMap;
 
 type Mapping = OrderMap<&'static str, Query>;
@@ -29,6 +34,11 @@ fn from_mapping() -> Result<Mapping, Error> {
         "include=author" => Query::builder()
             .include("author")
             .build()?,
+        "include=author&locale=en&dryRun=true" => Query::builder()
+            .include("author")
+            .extra("locale", "en")
+            .extra("dryRun", "true")
+            .build()?,
         "include=author%2Ccomments%2Ccomments.author" => Query::builder()
             .include("author")
             .include("comments")
@@ -46,6 +56,19 @@ fn from_mapping() -> Result<Mapping, Error> {
         "page%5Bnumber%5D=2&page%5Bsize%5D=15" => Query::builder()
             .page(2, Some(15))
             .build()?,
+        "page%5Bafter%5D=YWJj&page%5Bsize%5D=10" => Query::builder()
+            .after("YWJj", Some(10))
+            .build()?,
+        "page%5Bafter%5D=YWJj&page%5Bbefore%5D=ZGVm" => Query::builder()
+            .after("YWJj", None)
+            .before("ZGVm", None)
+            .build()?,
+        "page%5Boffset%5D=20&page%5Blimit%5D=10" => Query::builder()
+            .offset(20, Some(10))
+            .build()?,
+        "page%5Boffset%5D=5" => Query::builder()
+            .offset(5, None)
+            .build()?,
         "sort=-published-at" => Query::builder()
             .sort("published-at", Direction::Desc)
             .build()?,
@@ -125,3 +148,506 @@ fn query_to_vec() {
         assert_eq!(actual, expected.to_owned().into_bytes());
     }
 }
+
+fn page_links(query: &Query, total: u64) -> Vec<(String, String)> {
+    let base = "https://example.com/articles?page%5Bnumber%5D=7".parse().unwrap();
+
+    query::page_links(query, &base, total)
+        .unwrap()
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn query_page_links() {
+    let base = "https://example.com/articles";
+    let link = |number: u64| match number {
+        1 => format!("{}?include=author&page%5Bsize%5D=10", base),
+        _ => format!(
+            "{}?include=author&page%5Bnumber%5D={}&page%5Bsize%5D=10",
+            base, number
+        ),
+    };
+
+    let query = Query::builder().include("author").page(3, Some(10)).build().unwrap();
+
+    assert_eq!(
+        page_links(&query, 30),
+        vec![
+            ("self".to_owned(), link(3)),
+            ("first".to_owned(), link(1)),
+            ("prev".to_owned(), link(2)),
+            ("last".to_owned(), link(3)),
+        ]
+    );
+
+    assert_eq!(
+        page_links(&query, 31),
+        vec![
+            ("self".to_owned(), link(3)),
+            ("first".to_owned(), link(1)),
+            ("prev".to_owned(), link(2)),
+            ("next".to_owned(), link(4)),
+            ("last".to_owned(), link(4)),
+        ]
+    );
+
+    let query = Query::builder().include("author").page(1, Some(10)).build().unwrap();
+
+    assert_eq!(
+        page_links(&query, 0),
+        vec![
+            ("self".to_owned(), link(1)),
+            ("first".to_owned(), link(1)),
+            ("last".to_owned(), link(1)),
+        ]
+    );
+
+    let links = page_links(&Query::new(), 100);
+    assert!(links.iter().all(|&(_, ref value)| value == base));
+    let query = Query::builder().page(1, Some(10)).build().unwrap();
+    let links = page_links(&query, u64::max_value());
+    let last = u64::max_value() / 10 + 1;
+    let last = format!("{}?page%5Bnumber%5D={}&page%5Bsize%5D=10", base, last);
+
+    assert_eq!(links.last(), Some(&("last".to_owned(), last)));
+}
+
+#[test]
+fn query_cursor_links() {
+    let base = "https://example.com/articles".parse().unwrap();
+    let query = Query::builder()
+        .sort("created-at", Direction::Desc)
+        .after("YWJj", Some(10))
+        .build()
+        .unwrap();
+
+    let links = query::cursor_links(&query, &base, Some("ZGVm"), Some("Z2hp"))
+        .unwrap()
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect::<Vec<_>>();
+
+    let link = |page: &str| {
+        format!(
+            "https://example.com/articles?{}page%5Bsize%5D=10&sort=-created-at",
+            page
+        )
+    };
+
+    assert_eq!(
+        links,
+        vec![
+            ("self".to_owned(), link("page%5Bafter%5D=YWJj&")),
+            ("first".to_owned(), link("")),
+            ("prev".to_owned(), link("page%5Bbefore%5D=ZGVm&")),
+            ("next".to_owned(), link("page%5Bafter%5D=Z2hp&")),
+        ]
+    );
+
+    assert!(query::page_links(&query, &base, 100).is_err());
+    assert!(query::from_str("page%5Bafter%5D=YWJj&page%5Bnumber%5D=2").is_err());
+}
+
+#[test]
+fn query_offset() {
+    let query = query::from_str("page%5Blimit%5D=10").unwrap();
+    let page = query.page.unwrap();
+
+    assert_eq!(page.offset(), Some(0));
+    assert_eq!(page.size(), Some(10));
+
+    let sources = &[
+        "page%5Boffset%5D=20&page%5Bnumber%5D=2",
+        "page%5Blimit%5D=20&page%5Bsize%5D=2",
+        "page%5Boffset%5D=20&page%5Bafter%5D=YWJj",
+    ];
+
+    for source in sources {
+        assert!(query::from_str(source).is_err());
+    }
+}
+
+#[test]
+fn query_offset_links() {
+    let link = |offset: u64| {
+        format!(
+            "https://example.com/articles?page%5Boffset%5D={}&page%5Blimit%5D=10",
+            offset
+        )
+    };
+
+    let query = Query::builder().offset(15, Some(10)).build().unwrap();
+
+    assert_eq!(
+        page_links(&query, 31),
+        vec![
+            ("self".to_owned(), link(15)),
+            ("first".to_owned(), link(0)),
+            ("prev".to_owned(), link(5)),
+            ("next".to_owned(), link(25)),
+            ("last".to_owned(), link(30)),
+        ]
+    );
+
+    let query = Query::builder().offset(30, Some(10)).build().unwrap();
+
+    assert_eq!(
+        page_links(&query, 30),
+        vec![
+            ("self".to_owned(), link(30)),
+            ("first".to_owned(), link(0)),
+            ("prev".to_owned(), link(20)),
+            ("last".to_owned(), link(20)),
+        ]
+    );
+}
+
+#[test]
+fn query_filter() {
+    let path = |value: &str| value.parse::<Path>().unwrap();
+    let cases = vec![
+        (
+            "filter[age][gt]=21&filter[name][like]=foo*",
+            Filter::And(vec![
+                Filter::Compare(path("age"), Operator::Gt, "21".into()),
+                Filter::Compare(path("name"), Operator::Like, "foo*".into()),
+            ]),
+        ),
+        (
+            "filter[id]=1,2,3",
+            Filter::In(path("id"), vec!["1".into(), "2".into(), "3".into()]),
+        ),
+        (
+            "filter[users.name][ne]=Dan",
+            Filter::Compare(path("users.name"), Operator::Ne, "Dan".into()),
+        ),
+        (
+            "filter[age][gte]=21&filter[age][lt]=65",
+            Filter::And(vec![
+                Filter::Compare(path("age"), Operator::Gte, "21".into()),
+                Filter::Compare(path("age"), Operator::Lt, "65".into()),
+            ]),
+        ),
+        (
+            "filter[not][id][in][0]=1",
+            Filter::Not(Box::new(Filter::In(path("id"), vec!["1".into()]))),
+        ),
+        (
+            "filter[or][0][users][name]=Dan&filter[or][1][age][not][gt]=21",
+            Filter::Or(vec![
+                Filter::Compare(path("users.name"), Operator::Eq, "Dan".into()),
+                Filter::Not(Box::new(Filter::Compare(
+                    path("age"),
+                    Operator::Gt,
+                    "21".into(),
+                ))),
+            ]),
+        ),
+        (
+            "filter[and][0][age][gt]=21&filter[and][1][age][gt]=30",
+            Filter::And(vec![
+                Filter::Compare(path("age"), Operator::Gt, "21".into()),
+                Filter::Compare(path("age"), Operator::Gt, "30".into()),
+            ]),
+        ),
+    ];
+
+    for (source, expected) in cases {
+        let query = query::from_str(source).unwrap();
+        let filter = Filter::from_map(&query.filter).unwrap();
+
+        assert_eq!(filter, expected, "{}", source);
+
+        let mut query = Query::new();
+        query.filter = expected.to_map();
+
+        let query = query::from_str(&query::to_string(&query).unwrap()).unwrap();
+        assert_eq!(Filter::from_map(&query.filter).unwrap(), expected, "{}", source);
+    }
+
+    assert!(query::from_str("filter[age][gt]=21&filter[or]=21")
+        .and_then(|query| Filter::from_map(&query.filter))
+        .is_err());
+}
+
+#[test]
+fn query_apply_objects() {
+    let objects = (1..11)
+        .map(|id| {
+            let mut object = Object::new("articles".parse().unwrap(), id.to_string());
+            let author = Identifier::new("users".parse().unwrap(), (id % 3).to_string());
+
+            object
+                .attributes
+                .insert("rating".parse().unwrap(), (id % 4).into());
+            object.relationships.insert(
+                "author".parse().unwrap(),
+                Relationship::new(Some(author).into()),
+            );
+
+            object
+        })
+        .collect::<Vec<_>>();
+
+    let apply = |source: &str| {
+        let query = query::from_str(source).unwrap();
+        let (page, total) = query::apply_objects(objects.clone(), &query).unwrap();
+        let ids = page.into_iter().map(|object| object.id).collect::<Vec<_>>();
+
+        (ids.join(","), total)
+    };
+
+    assert_eq!(apply(""), ("1,2,3,4,5,6,7,8,9,10".to_owned(), 10));
+    assert_eq!(apply("filter[author]=1"), ("1,4,7,10".to_owned(), 4));
+    assert_eq!(apply("filter[rating]=2&sort=-id"), ("10,6,2".to_owned(), 3));
+    assert_eq!(apply("filter[id][]=3&filter[id][]=5"), ("3,5".to_owned(), 2));
+    assert_eq!(apply("sort=-rating,id&page[size]=3"), ("3,7,2".to_owned(), 10));
+    assert_eq!(apply("sort=rating&page[offset]=8"), ("3,7".to_owned(), 10));
+
+    assert_eq!(apply("filter[id]=3,5,11"), ("3,5".to_owned(), 2));
+    assert_eq!(apply("filter[rating][gt]=2"), ("3,7".to_owned(), 2));
+    assert_eq!(apply("filter[rating][not][lte]=2"), ("3,7".to_owned(), 2));
+    assert_eq!(apply("filter[id][like]=1*"), ("1,10".to_owned(), 2));
+    assert_eq!(apply("filter[rating][ne]=0&filter[author]=0"), ("3,6,9".to_owned(), 3));
+
+    assert!(query::from_str("filter[rating][gt]=2&filter[or]=2")
+        .and_then(|query| query::apply_objects(objects.clone(), &query))
+        .is_err());
+
+    let objects = ["b", "10", "a", "9", "1e400"]
+        .iter()
+        .map(|id| Object::new("articles".parse().unwrap(), id.to_string()))
+        .collect();
+
+    let query = query::from_str("sort=id").unwrap();
+    let (page, _) = query::apply_objects(objects, &query).unwrap();
+    let ids = page.into_iter().map(|object| object.id).collect::<Vec<_>>();
+
+    assert_eq!(ids, vec!["9", "10", "1e400", "a", "b"]);
+}
+
+fn select(conn: &rusqlite::Connection, columns: &sql::Columns, source: &str) -> Vec<i64> {
+    use rusqlite::types::Value as SqlValue;
+
+    let query = query::from_str(source).unwrap();
+    let fragment = sql::from_query(&query, columns).unwrap().to_fragment();
+    let params = fragment.params.into_iter().map(|value| match value {
+        Value::Bool(value) => SqlValue::Integer(value as i64),
+        Value::Number(value) => match value.as_i64() {
+            Some(value) => SqlValue::Integer(value),
+            None => SqlValue::Real(value.as_f64().unwrap()),
+        },
+        Value::String(value) => SqlValue::Text(value),
+        _ => SqlValue::Null,
+    });
+
+    let sql = format!("SELECT id FROM articles {}", fragment.sql);
+    let mut stmt = conn.prepare(&sql).unwrap();
+    let rows = stmt.query_map(params, |row| row.get(0)).unwrap();
+
+    rows.map(Result::unwrap).collect()
+}
+
+#[test]
+fn query_sql() {
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    let mut columns = sql::Columns::new();
+
+    conn.execute_batch(
+        "CREATE TABLE articles (id INTEGER, title TEXT, rating INTEGER);
+         INSERT INTO articles VALUES (1, 'Rust 101', 3);
+         INSERT INTO articles VALUES (2, 'Rust 2018', 5);
+         INSERT INTO articles VALUES (3, 'Serde_JSON', 4);
+         INSERT INTO articles VALUES (4, '100% Rust', NULL);
+         INSERT INTO articles VALUES (5, 'Rust''s macros', 2);",
+    ).unwrap();
+
+    columns.insert("id".parse().unwrap(), "id").unwrap();
+    columns.insert("title".parse().unwrap(), "articles.title").unwrap();
+    columns.insert("rating".parse().unwrap(), "rating").unwrap();
+
+    assert_eq!(select(&conn, &columns, ""), vec![1, 2, 3, 4, 5]);
+    assert_eq!(select(&conn, &columns, "filter[id]=2,4"), vec![2, 4]);
+    assert_eq!(select(&conn, &columns, "filter[rating][gte]=4"), vec![2, 3]);
+    assert_eq!(select(&conn, &columns, "filter[title][like]=Rust*"), vec![1, 2, 5]);
+    assert_eq!(select(&conn, &columns, "filter[title][like]=*%*"), vec![4]);
+    assert_eq!(select(&conn, &columns, "filter[title][like]=*e_J*"), vec![3]);
+    assert_eq!(select(&conn, &columns, "filter[title]=Rust's macros"), vec![5]);
+    assert_eq!(
+        select(&conn, &columns, "filter[or][0][id]=1&filter[or][1][rating][lt]=3"),
+        vec![1, 5]
+    );
+    assert_eq!(
+        select(&conn, &columns, "filter[not][title][like]=Rust*&sort=-id"),
+        vec![4, 3]
+    );
+    assert_eq!(
+        select(&conn, &columns, "sort=-rating,id&page[number]=2&page[size]=2"),
+        vec![1, 5]
+    );
+    assert_eq!(select(&conn, &columns, "sort=id&page[offset]=3"), vec![4, 5]);
+
+    let rejected = vec![
+        "sort=body",
+        "filter[body]=1",
+        "filter[title;DROP TABLE articles]=1",
+        "filter[or][0][body]=1",
+        "filter[title][like][0]=1",
+        "page[cursor]=abc",
+    ];
+
+    for source in rejected {
+        let query = query::from_str(source);
+        let result = query.and_then(|query| sql::from_query(&query, &columns));
+
+        assert!(result.is_err(), "{}", source);
+    }
+
+    assert!(columns.insert("body".parse().unwrap(), "body; --").is_err());
+    assert!(columns.insert("body".parse().unwrap(), "\"body\"").is_err());
+}
+
+#[test]
+fn query_validate() {
+    let key = |value: &str| value.parse().unwrap();
+    let mut registry = Registry::new();
+
+    let mut articles = Schema::new(key("articles"));
+    articles.attributes.insert(key("title"));
+    articles
+        .relationships
+        .insert(key("author"), Relation::HasOne(key("users")));
+    articles
+        .relationships
+        .insert(key("comments"), Relation::HasMany(key("comments")));
+
+    let mut comments = Schema::new(key("comments"));
+    comments.attributes.insert(key("body"));
+    comments
+        .relationships
+        .insert(key("author"), Relation::HasOne(key("users")));
+
+    let mut users = Schema::new(key("users"));
+    users.attributes.insert(key("name"));
+
+    registry.insert(articles);
+    registry.insert(comments);
+    registry.insert(users);
+
+    let validate = |source: &str| {
+        let query = query::from_str(source).unwrap();
+
+        query
+            .validate("articles", &registry)
+            .into_iter()
+            .map(|error| {
+                assert_eq!(error.status, Some(json_api::http::StatusCode::BAD_REQUEST));
+                error.source.and_then(|source| source.parameter).unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert!(validate("").is_empty());
+    assert!(validate("fields[articles]=title,author&fields[users]=name").is_empty());
+    assert!(validate("include=author,comments.author").is_empty());
+    assert!(validate("sort=-title,id,author.name").is_empty());
+
+    assert_eq!(validate("fields[articles]=body,author"), vec!["fields[articles]"]);
+    assert_eq!(validate("fields[posts]=title"), vec!["fields[posts]"]);
+    assert_eq!(validate("include=title,comments.author.posts"), vec!["include"; 2]);
+    assert_eq!(validate("sort=body,comments.body,author"), vec!["sort"; 3]);
+}
+
+#[test]
+fn query_extra() {
+    let query = query::from_str("geo[lat]=51.5&geo[lng]=0&tag[]=a&tag[]=b").unwrap();
+
+    let geo = query.extra.get("geo").and_then(Value::as_object).unwrap();
+
+    assert_eq!(geo.get("lat"), Some(&Value::from("51.5")));
+    assert_eq!(geo.get("lng"), Some(&Value::from("0")));
+    assert_eq!(query.extra.get("tag"), Some(&Value::from(vec!["a", "b"])));
+
+    let source = query::to_string(&query).unwrap();
+    assert_eq!(query::from_str(&source).unwrap(), query);
+
+    assert!(Query::builder().extra("sort", "title").build().is_err());
+}
+
+#[test]
+fn query_policy() {
+    let mut policy = QueryPolicy::new();
+
+    policy.default_size = Some(25);
+    policy.max_size = Some(100);
+    policy.default_sort = "title".parse().unwrap();
+    policy.default_fields.insert("users".parse().unwrap(), "name".parse().unwrap());
+
+    let apply = |policy: &QueryPolicy, source: &str| {
+        let mut query = query::from_str(source).unwrap();
+
+        policy.apply(&mut query).map(|_| query::to_string(&query).unwrap())
+    };
+
+    let expected = Query::builder()
+        .fields("users", vec!["name"])
+        .page(1, Some(25))
+        .sort("title", Direction::Asc)
+        .build()
+        .unwrap();
+
+    assert_eq!(apply(&policy, "").unwrap(), query::to_string(&expected).unwrap());
+
+    let expected = Query::builder()
+        .fields("users", vec!["email"])
+        .offset(10, Some(100))
+        .sort("id", Direction::Desc)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        apply(&policy, "fields[users]=email&page[offset]=10&page[limit]=500&sort=-id")
+            .unwrap(),
+        query::to_string(&expected).unwrap()
+    );
+
+    let expected = Query::builder()
+        .fields("users", vec!["name"])
+        .after("abc", Some(50))
+        .sort("title", Direction::Asc)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        apply(&policy, "page[after]=abc&page[size]=50").unwrap(),
+        query::to_string(&expected).unwrap()
+    );
+
+    policy.overflow = Overflow::Reject;
+
+    let e = apply(&policy, "page[number]=2&page[size]=500").unwrap_err();
+    let error = json_api::doc::ErrorObject::from(&e);
+
+    assert_eq!(error.status, Some(json_api::http::StatusCode::BAD_REQUEST));
+    assert_eq!(
+        error.source.and_then(|source| source.parameter),
+        Some("page[size]".to_owned())
+    );
+
+    assert!(apply(&policy, "page[number]=2&page[size]=100").is_ok());
+
+    // A default that exceeds the maximum is lowered instead of rejected.
+    policy.default_size = Some(200);
+
+    let expected = Query::builder()
+        .fields("users", vec!["name"])
+        .page(1, Some(100))
+        .sort("title", Direction::Asc)
+        .build()
+        .unwrap();
+
+    assert_eq!(apply(&policy, "").unwrap(), query::to_string(&expected).unwrap());
+    assert!(apply(&policy, "page[number]=2&page[size]=101").is_err());
+}